@@ -0,0 +1,32 @@
+mod bench_util;
+
+use bench_util::bench;
+use polynomial::Polynomial;
+use std::hint::black_box;
+
+fn main() {
+    let mut p = Polynomial::new();
+    for power in 0..=200 {
+        p.insert(power, 1.0 / (power + 1) as f32);
+    }
+    let xs = (0..10_000)
+        .map(|i| -1.0 + 2.0 * i as f32 / 10_000.0)
+        .collect::<Vec<f32>>();
+    bench("at twice", 20, || {
+        let p = black_box(&p);
+        black_box(&xs)
+            .iter()
+            .map(|&x| p.at(x) + p.at(-x))
+            .sum::<f32>()
+    });
+    bench("at_pair", 20, || {
+        let p = black_box(&p);
+        black_box(&xs)
+            .iter()
+            .map(|&x| {
+                let (at_x, at_minus_x) = p.at_pair(x);
+                at_x + at_minus_x
+            })
+            .sum::<f32>()
+    });
+}
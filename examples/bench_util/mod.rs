@@ -0,0 +1,29 @@
+//! Minimal timing harness shared by the benchmark examples.
+//! - Run them in release mode, e.g. `cargo run --release --example at_pair`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Runs `f` a few times to warm up, then times `samples` runs and prints the min, median and max.
+/// - Results pass through `black_box`, so the optimizer can not discard the measured work.
+pub fn bench<T>(name: &str, samples: usize, mut f: impl FnMut() -> T) {
+    for _ in 0..3 {
+        black_box(f());
+    }
+    let mut times = (0..samples.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect::<Vec<Duration>>();
+    times.sort();
+    println!(
+        "{:<36} min {:>12.3?}  median {:>12.3?}  max {:>12.3?}  ({} samples)",
+        name,
+        times[0],
+        times[times.len() / 2],
+        times[times.len() - 1],
+        times.len()
+    );
+}
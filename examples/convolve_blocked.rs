@@ -0,0 +1,29 @@
+mod bench_util;
+
+use bench_util::bench;
+use polynomial::Polynomial;
+use std::hint::black_box;
+
+fn main() {
+    let mut signal = Polynomial::new();
+    for power in 0..=100_000 {
+        signal.insert(power, ((power % 13) as f32 - 6.0) * 0.5);
+    }
+    let mut kernel = Polynomial::new();
+    for power in 0..16 {
+        kernel.insert(power, 1.0 / (power + 1) as f32);
+    }
+    let product = &signal * &kernel;
+    bench("Mul", 10, || black_box(&signal) * black_box(&kernel));
+    for &block_size in [64, 256, 1024, 4096].iter() {
+        bench(
+            &format!("convolve_blocked, block_size = {}", block_size),
+            10,
+            || black_box(&signal).convolve_blocked(black_box(&kernel), block_size),
+        );
+        assert_eq!(
+            signal.convolve_blocked(&kernel, block_size).degree(),
+            product.degree()
+        );
+    }
+}
@@ -0,0 +1,277 @@
+//! Helpers on dense `f64` coefficient vectors, `p[k]` being the coefficient of `x^k`.
+//! - Vectors are kept trimmed, i.e. the last element (if any) is non-zero.
+
+use crate::complex::Complex;
+
+/// Removes trailing zero coefficients.
+pub(crate) fn trim(mut p: Vec<f64>) -> Vec<f64> {
+    while p.last() == Some(&0.0) {
+        p.pop();
+    }
+    p
+}
+
+/// Number of sign changes in the sequence of non-zero coefficients.
+pub(crate) fn sign_variations(p: &[f64]) -> usize {
+    let signs = p.iter().filter(|&&c| c != 0.0).map(|c| c.signum());
+    signs
+        .clone()
+        .zip(signs.skip(1))
+        .filter(|(s, t)| s != t)
+        .count()
+}
+
+/// `p(x + shift)`, by repeated synthetic division.
+pub(crate) fn taylor_shift(p: &[f64], shift: f64) -> Vec<f64> {
+    let mut shifted = p.to_vec();
+    let n = shifted.len();
+    for i in 0..n {
+        for j in (i..n - 1).rev() {
+            shifted[j] += shift * shifted[j + 1];
+        }
+    }
+    shifted
+}
+
+/// `x^n p(1 / x)` for `n = deg p`.
+pub(crate) fn reverse(p: &[f64]) -> Vec<f64> {
+    trim(p.iter().rev().cloned().collect())
+}
+
+/// `p(-x)`.
+pub(crate) fn reflect(p: &[f64]) -> Vec<f64> {
+    p.iter()
+        .enumerate()
+        .map(|(k, &c)| if k % 2 == 1 { -c } else { c })
+        .collect()
+}
+
+/// `p'(x)`.
+pub(crate) fn derivative(p: &[f64]) -> Vec<f64> {
+    p.iter()
+        .enumerate()
+        .skip(1)
+        .map(|(k, &c)| k as f64 * c)
+        .collect()
+}
+
+/// Quotient and remainder of `a / b`, `b` must be non-empty.
+/// - Remainder coefficients of magnitude at most `tol` are treated as zero.
+pub(crate) fn div_rem(a: &[f64], b: &[f64], tol: f64) -> (Vec<f64>, Vec<f64>) {
+    let mut remainder = a.to_vec();
+    if a.len() < b.len() {
+        return (vec![], trim(remainder));
+    }
+    let mut quotient = vec![0.0; a.len() - b.len() + 1];
+    let lead = b[b.len() - 1];
+    for k in (0..quotient.len()).rev() {
+        let factor = remainder[k + b.len() - 1] / lead;
+        quotient[k] = factor;
+        for (j, &bj) in b.iter().enumerate() {
+            remainder[k + j] -= factor * bj;
+        }
+        remainder[k + b.len() - 1] = 0.0;
+    }
+    for c in remainder.iter_mut() {
+        if c.abs() <= tol {
+            *c = 0.0;
+        }
+    }
+    (trim(quotient), trim(remainder))
+}
+
+/// `p(z)` by Horner's method.
+pub(crate) fn at_complex(p: &[f64], z: Complex) -> Complex {
+    p.iter().rev().fold(Complex::new(0.0, 0.0), |acc, &c| {
+        acc * z + Complex::new(c, 0.0)
+    })
+}
+
+/// Number of roots of `p` inside the rectangle `re.0 < Re z < re.1`, `im.0 < Im z < im.1` by the argument principle.
+/// - Accumulates the change in argument of `p` along the boundary, subdividing steps where it turns quickly.
+/// - Meaningless if a root lies on the boundary.
+pub(crate) fn winding_number(p: &[f64], re: (f64, f64), im: (f64, f64)) -> i64 {
+    fn arg_change(
+        p: &[f64],
+        a: Complex,
+        b: Complex,
+        pa: Complex,
+        pb: Complex,
+        depth: usize,
+    ) -> f64 {
+        let change = (pb / pa).arg();
+        if change.abs() <= std::f64::consts::FRAC_PI_4 || depth == 0 {
+            return change;
+        }
+        let mid = (a + b).scale(0.5);
+        let pm = at_complex(p, mid);
+        arg_change(p, a, mid, pa, pm, depth - 1) + arg_change(p, mid, b, pm, pb, depth - 1)
+    }
+    let corners = [
+        Complex::new(re.0, im.0),
+        Complex::new(re.1, im.0),
+        Complex::new(re.1, im.1),
+        Complex::new(re.0, im.1),
+    ];
+    let steps = 16 * p.len();
+    let mut total = 0.0;
+    for k in 0..4 {
+        let (start, end) = (corners[k], corners[(k + 1) % 4]);
+        let point = |i: usize| start + (end - start).scale(i as f64 / steps as f64);
+        let mut previous = at_complex(p, start);
+        for i in 1..=steps {
+            let value = at_complex(p, point(i));
+            total += arg_change(p, point(i - 1), point(i), previous, value, 20);
+            previous = value;
+        }
+    }
+    (total / std::f64::consts::TAU).round() as i64
+}
+
+/// Largest absolute value of coefficients.
+pub(crate) fn max_norm(p: &[f64]) -> f64 {
+    p.iter().fold(0.0, |max, c| max.max(c.abs()))
+}
+
+/// Monic greatest common divisor by the euclidean algorithm.
+/// - Remainders are considered zero when they are negligible relative to the inputs.
+pub(crate) fn gcd(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let tol = 1e-10 * max_norm(a).max(max_norm(b));
+    let (mut a, mut b) = (trim(a.to_vec()), trim(b.to_vec()));
+    while !b.is_empty() {
+        let (_, remainder) = div_rem(&a, &b, tol);
+        a = b;
+        b = remainder;
+    }
+    match a.last() {
+        Some(&lead) => a.iter().map(|c| c / lead).collect(),
+        None => a,
+    }
+}
+
+/// Product of the distinct irreducible factors of `p`, i.e. `p / gcd(p, p')`.
+pub(crate) fn square_free(p: &[f64]) -> Vec<f64> {
+    let g = gcd(p, &derivative(p));
+    if g.len() <= 1 {
+        return trim(p.to_vec());
+    }
+    div_rem(p, &g, 0.0).0
+}
+
+/// Companion matrix of `p` (degree at least 1) as rows, in upper hessenberg form.
+/// - First row holds `-p[n - 1] / p[n], ..., -p[0] / p[n]`, subdiagonal holds ones.
+pub(crate) fn companion_matrix(p: &[f64]) -> Vec<Vec<f64>> {
+    let n = p.len() - 1;
+    let lead = p[n];
+    let mut matrix = vec![vec![0.0; n]; n];
+    for j in 0..n {
+        matrix[0][j] = -p[n - 1 - j] / lead;
+    }
+    for i in 1..n {
+        matrix[i][i - 1] = 1.0;
+    }
+    matrix
+}
+
+/// Companion pencil `(a, b)` of `p` (degree at least 1) as rows, whose generalized eigenvalues are the roots.
+/// - `a` has first row `-p[n - 1], ..., -p[0]` and ones on the subdiagonal, `b = diag(p[n], 1, ..., 1)`.
+pub(crate) fn companion_pencil(p: &[f64]) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+    let n = p.len() - 1;
+    let mut a = vec![vec![0.0; n]; n];
+    let mut b = vec![vec![0.0; n]; n];
+    for j in 0..n {
+        a[0][j] = -p[n - 1 - j];
+        b[j][j] = 1.0;
+    }
+    for i in 1..n {
+        a[i][i - 1] = 1.0;
+    }
+    b[0][0] = p[n];
+    (a, b)
+}
+
+/// Upper bound on the absolute values of the roots of `p` (degree at least 1).
+/// - Smaller of Fujiwara's bound `2 max |a[n-k] / a[n]|^(1/k)` (with `a[0]` halved)
+///   and, when all coefficients are non-zero, Kojima's bound `max(2 |a[k-1] / a[k]|, |a[0] / a[1]|)`.
+pub(crate) fn root_modulus_upper_bound(p: &[f64]) -> f64 {
+    let n = p.len() - 1;
+    let fujiwara = (1..=n)
+        .map(|k| {
+            let c = if k == n { p[0] / 2.0 } else { p[n - k] };
+            (c / p[n]).abs().powf(1.0 / k as f64)
+        })
+        .fold(0.0, f64::max)
+        * 2.0;
+    if p.contains(&0.0) {
+        return fujiwara;
+    }
+    let kojima = (2..=n)
+        .map(|k| 2.0 * (p[k - 1] / p[k]).abs())
+        .fold((p[0] / p[1]).abs(), f64::max);
+    fujiwara.min(kojima)
+}
+
+/// Upper bound on the positive roots of `p`, `0.0` if `p` has none by Descartes' rule of signs.
+pub(crate) fn positive_root_upper_bound(p: &[f64]) -> f64 {
+    let n = p.len() - 1;
+    let lead = p[n];
+    // Kioustelidis' bound on the coefficients of sign opposite to the leading one
+    (0..n)
+        .filter(|&k| p[k] * lead < 0.0)
+        .map(|k| 2.0 * (-p[k] / lead).powf(1.0 / (n - k) as f64))
+        .fold(0.0, f64::max)
+}
+
+/// Disjoint intervals each containing exactly one positive root of the square-free `p` with `p(0) != 0`.
+/// - Vincent–Akritas–Strzeboński continued fraction method, exact roots come back as degenerate intervals.
+/// - `None` if the subdivision does not finish within 10 000 steps, e.g. for NaN coefficients.
+pub(crate) fn isolate_positive_roots_vas(p: &[f64]) -> Option<Vec<(f64, f64)>> {
+    let is_zero = |q: &[f64], c: f64| c.abs() <= 1e-12 * max_norm(q);
+    let upper_bound = positive_root_upper_bound(p);
+    let mut intervals = vec![];
+    // Each entry is a transformed polynomial and the mobius map (a x + b) / (c x + d) back to the original variable
+    let mut stack = vec![(p.to_vec(), (1f64, 0f64, 0f64, 1f64))];
+    let mut budget = 10_000;
+    while let Some((mut q, (a, mut b, c, mut d))) = stack.pop() {
+        if budget == 0 {
+            return None;
+        }
+        budget -= 1;
+        let variations = sign_variations(&q);
+        if variations == 0 {
+            continue;
+        }
+        if variations == 1 {
+            let left = b / d;
+            let right = if c == 0.0 { upper_bound } else { a / c };
+            intervals.push((left.min(right), left.max(right)));
+            continue;
+        }
+        // Shift by a lower bound on the positive roots when it is at least 1
+        let reciprocal_bound = positive_root_upper_bound(&reverse(&q));
+        if reciprocal_bound > 0.0 && 1.0 / reciprocal_bound >= 1.0 {
+            let lower_bound = 1.0 / reciprocal_bound;
+            q = taylor_shift(&q, lower_bound);
+            b += a * lower_bound;
+            d += c * lower_bound;
+            if is_zero(&q, q[0]) {
+                intervals.push((b / d, b / d));
+                q.remove(0);
+            }
+        }
+        // Roots in (0, 1) and (1, inf)
+        let mut right = taylor_shift(&q, 1.0);
+        if is_zero(&right, right[0]) {
+            intervals.push(((a + b) / (c + d), (a + b) / (c + d)));
+            right.remove(0);
+        }
+        let mut left = taylor_shift(&reverse(&q), 1.0);
+        if is_zero(&left, left[0]) {
+            left.remove(0);
+        }
+        stack.push((left, (b, a + b, d, c + d)));
+        stack.push((right, (a, a + b, c, c + d)));
+    }
+    intervals.sort_by(|s, t| s.0.total_cmp(&t.0));
+    Some(intervals)
+}
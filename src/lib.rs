@@ -1,6 +1,14 @@
-use std::collections::HashMap;
+use complex::Complex;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::fmt;
-use std::ops::{Add, AddAssign, Div, Mul, Rem, Sub, SubAssign};
+use std::hash::{Hash, Hasher};
+use std::iter::{FromIterator, Sum};
+use std::ops::{Add, AddAssign, Div, Index, Mul, Neg, Rem, Sub, SubAssign};
+use std::str::FromStr;
+mod complex;
+mod dense;
+mod linalg;
 mod tests;
 
 #[macro_export]
@@ -9,7 +17,7 @@ macro_rules! polynomial (
         {
             let mut p = Polynomial::new();
             $(
-                p.insert($power, $coeff);
+                p.add_term($power, $coeff);
             )*
             p
         }
@@ -17,18 +25,26 @@ macro_rules! polynomial (
 );
 
 /// Invariant: Only terms with non-zero coefficients are stored in memory.
+/// - Terms are kept ordered by power, so they can be walked in descending power without sorting.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Polynomial {
-    coeff_of_power: HashMap<usize, f32>,
+    coeff_of_power: BTreeMap<usize, f32>,
 }
 
 impl Polynomial {
     pub fn new() -> Self {
         Polynomial {
-            coeff_of_power: HashMap::new(),
+            coeff_of_power: BTreeMap::new(),
         }
     }
 
+    /// Single term polynomial `coeff * x^power`, zero polynomial if `coeff` is zero.
+    pub fn monomial(power: usize, coeff: f32) -> Self {
+        let mut monomial = Self::new();
+        monomial.insert(power, coeff);
+        monomial
+    }
+
     pub fn insert(&mut self, power: usize, coeff: f32) {
         if coeff == 0.0 {
             self.coeff_of_power.remove(&power);
@@ -37,16 +53,205 @@ impl Polynomial {
         self.coeff_of_power.insert(power, coeff);
     }
 
+    /// Adds `coeff * x^power`, accumulating onto any existing term of the same power.
+    pub fn add_term(&mut self, power: usize, coeff: f32) {
+        let prev_coeff = self.coeff_of_power.get(&power).cloned().unwrap_or(0.0);
+        self.insert(power, prev_coeff + coeff);
+    }
+
+    /// `self += factor * other`, without allocating the scaled `other`.
+    pub fn add_scaled(&mut self, other: &Polynomial, factor: f32) {
+        for (&power, &coeff) in other.coeff_of_power.iter() {
+            self.add_term(power, factor * coeff);
+        }
+    }
+
     pub fn degree(&self) -> Option<usize> {
         self.coeff_of_power.iter().map(|(&power, &_)| power).max()
     }
 
+    /// `(degree, leading coefficient)` in one traversal, `None` for zero polynomial.
+    pub fn leading_term(&self) -> Option<(usize, f32)> {
+        self.coeff_of_power
+            .iter()
+            .max_by_key(|(&power, &_)| power)
+            .map(|(&power, &coeff)| (power, coeff))
+    }
+
+    /// Coefficient of the highest power term, `None` for zero polynomial.
+    pub fn leading_coefficient(&self) -> Option<f32> {
+        self.leading_term().map(|(_, coeff)| coeff)
+    }
+
+    /// Whether it is the zero polynomial, i.e. has no terms.
+    pub fn is_zero(&self) -> bool {
+        self.coeff_of_power.is_empty()
+    }
+
+    /// Whether it has no terms of positive power, zero polynomial included.
+    pub fn is_constant(&self) -> bool {
+        self.degree() <= Some(0)
+    }
+
+    /// Whether the leading coefficient is exactly `1.0`, zero polynomial is not monic.
+    pub fn is_monic(&self) -> bool {
+        self.leading_coefficient() == Some(1.0)
+    }
+
+    /// Coefficient of `x^power`, `0.0` if absent.
+    pub fn coeff(&self, power: usize) -> f32 {
+        self[power]
+    }
+
+    /// Value at `x` by Horner's method over the stored terms in descending power.
+    /// - Gaps between sparse powers are bridged by multiplying with `x^gap`.
     pub fn at(&self, x: f32) -> f32 {
-        let mut value = 0f32;
+        self.horner(x)
+    }
+
+    /// Value at `x`, or `None` if it overflows to infinity or is NaN.
+    pub fn at_checked(&self, x: f32) -> Option<f32> {
+        let value = self.at(x);
+        if value.is_finite() {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Quotient and remainder of division by `x - r`, by synthetic division.
+    /// - Remainder equals `self.at(r)`, zero polynomial gives `(0, 0.0)`.
+    pub fn divide_linear(&self, r: f32) -> (Polynomial, f32) {
+        let coeffs = self.to_dense();
+        let mut quotient = vec![0f32; coeffs.len().saturating_sub(1)];
+        let mut carry = 0f32;
+        for (power, &coeff) in coeffs.iter().enumerate().rev() {
+            carry = carry * r + coeff;
+            if power > 0 {
+                quotient[power - 1] = carry;
+            }
+        }
+        (Polynomial::from(quotient), carry)
+    }
+
+    /// Values at each of `xs`.
+    pub fn evaluate_many(&self, xs: &[f32]) -> Vec<f32> {
+        self.evaluate_all(xs)
+    }
+
+    /// Values at each of `xs` in order.
+    pub fn evaluate_all(&self, xs: &[f32]) -> Vec<f32> {
+        xs.iter().map(|&x| self.at(x)).collect()
+    }
+
+    /// Values at each of `xs` written into `out`, without allocating.
+    /// - `out` must have the same length as `xs`.
+    pub fn evaluate_many_into(&self, xs: &[f32], out: &mut [f32]) -> Result<(), LenMismatch> {
+        if xs.len() != out.len() {
+            return Err(LenMismatch {
+                expected: xs.len(),
+                actual: out.len(),
+            });
+        }
+        for (y, &x) in out.iter_mut().zip(xs) {
+            *y = self.at(x);
+        }
+        Ok(())
+    }
+
+    /// Value at the complex point `re + im i`, evaluated in `f64`, returned as `(re, im)`.
+    pub fn at_complex(&self, re: f64, im: f64) -> (f64, f64) {
+        let z = Complex::new(re, im);
+        let mut value = Complex::new(0.0, 0.0);
         for (&power, &coeff) in self.coeff_of_power.iter() {
-            value += coeff * x.powi(power as i32);
+            value = value + Complex::new(coeff as f64, 0.0) * z.powi(power);
+        }
+        (value.re, value.im)
+    }
+
+    /// Values at the `nx` by `ny` grid of complex points spanning `re_range` and `im_range` (inclusive).
+    /// - `grid[j][i]` is the value at the `i`th real and `j`th imaginary sample, as `(re, im)`.
+    pub fn sample_complex_grid(
+        &self,
+        re_range: (f64, f64),
+        im_range: (f64, f64),
+        nx: usize,
+        ny: usize,
+    ) -> Vec<Vec<(f64, f64)>> {
+        fn sample(range: (f64, f64), i: usize, n: usize) -> f64 {
+            match n {
+                1 => range.0,
+                _ => range.0 + (range.1 - range.0) * (i as f64 / (n - 1) as f64),
+            }
+        }
+        (0..ny)
+            .map(|j| {
+                let im = sample(im_range, j, ny);
+                (0..nx)
+                    .map(|i| self.at_complex(sample(re_range, i, nx), im))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Checks the sampling arguments shared by the plotting functions.
+    fn validate_plot_args(l: f32, r: f32, num_samples: usize) -> Result<(), &'static str> {
+        if num_samples < 2 {
+            return Err("Requested less than 2 samples for plotting.");
+        }
+        if l >= r || l.is_nan() || r.is_nan() {
+            return Err("Left bound must be strictly less than right bound.");
+        }
+        Ok(())
+    }
+
+    /// Figure with each curve `(poly, caption, color)` sampled at `num_samples` evenly spaced points from `l` to `r` (inclusive).
+    /// - Curves without a color get gnuplot's default one.
+    fn styled_figure(
+        curves: &[(&Polynomial, String, Option<&str>)],
+        l: f32,
+        r: f32,
+        num_samples: usize,
+    ) -> gnuplot::Figure {
+        use gnuplot::*;
+        let mut fg = Figure::new();
+        let axes = fg.axes2d();
+        let xs = (0..num_samples)
+            .map(|i| l + (r - l) * (i as f32 / (num_samples - 1) as f32))
+            .collect::<Vec<f32>>();
+        for (poly, caption, color) in curves.iter() {
+            let mut options = vec![Caption(caption.as_str()), LineWidth(1.0)];
+            if let Some(color) = color {
+                options.push(Color(color));
+            }
+            axes.lines(&xs, poly.evaluate_all(&xs), &options);
         }
-        value
+        axes.set_x_label("x", &[])
+            .set_y_label("y", &[])
+            .set_grid_options(true, &[LineStyle(SmallDot), Color("grey")])
+            .set_x_grid(true)
+            .set_y_grid(true)
+            .set_title(
+                &format!("plotted from {} to {} with {} samples", l, r, num_samples),
+                &[],
+            );
+        fg
+    }
+
+    /// Figure with each polynomial sampled at `num_samples` evenly spaced points from `l` to `r` (inclusive),
+    /// for the caller to customize further and output as they like.
+    pub fn build_figure(
+        polys: &[&Polynomial],
+        l: f32,
+        r: f32,
+        num_samples: usize,
+    ) -> Result<gnuplot::Figure, String> {
+        Polynomial::validate_plot_args(l, r, num_samples)?;
+        let curves = polys
+            .iter()
+            .map(|&poly| (poly, poly.to_latex(), None))
+            .collect::<Vec<_>>();
+        Ok(Polynomial::styled_figure(&curves, l, r, num_samples))
     }
 
     pub fn plot<'a>(
@@ -56,20 +261,140 @@ impl Polynomial {
         num_samples: usize,
         filename: &str,
     ) -> Result<(), &'a str> {
+        Polynomial::validate_plot_args(l, r, num_samples)?;
+        Polynomial::build_figure(polys, l, r, num_samples)
+            .expect("Arguments were validated above.")
+            .echo_to_file(&format!("{}.gnuplot", filename));
+        Ok(())
+    }
+
+    /// Like `plot`, but each polynomial comes with its caption and a gnuplot color name (e.g. `"red"` or `"#9400d3"`).
+    /// - An empty caption defaults to `to_latex`, the caption `plot` uses.
+    pub fn plot_styled<'a>(
+        polys: &[(&Polynomial, &str, &str)],
+        l: f32,
+        r: f32,
+        num_samples: usize,
+        filename: &str,
+    ) -> Result<(), &'a str> {
+        Polynomial::validate_plot_args(l, r, num_samples)?;
+        let curves = polys
+            .iter()
+            .map(|&(poly, caption, color)| {
+                let caption = if caption.is_empty() {
+                    poly.to_latex()
+                } else {
+                    caption.to_string()
+                };
+                (poly, caption, Some(color))
+            })
+            .collect::<Vec<_>>();
+        Polynomial::styled_figure(&curves, l, r, num_samples)
+            .echo_to_file(format!("{}.gnuplot", filename));
+        Ok(())
+    }
+
+    /// Like `plot`, but renders the image `filename` by running gnuplot, which must be installed.
+    /// - The extension of `filename` selects the format, either `.png` or `.svg`.
+    /// - `Ok` means the image was written, failures to run gnuplot or to produce the file are reported as `Err`.
+    pub fn plot_to_image(
+        polys: &[&Polynomial],
+        l: f32,
+        r: f32,
+        num_samples: usize,
+        filename: &str,
+    ) -> Result<(), String> {
+        let mut fg = Polynomial::build_figure(polys, l, r, num_samples)?;
+        let terminal = match std::path::Path::new(filename)
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some("png") => "pngcairo",
+            Some("svg") => "svg",
+            _ => {
+                return Err(format!(
+                    "Unsupported image format of '{}', expected a .png or .svg file.",
+                    filename
+                ))
+            }
+        };
+        // A stale image must not pass for a freshly rendered one
+        let _ = std::fs::remove_file(filename);
+        fg.set_terminal(terminal, filename);
+        fg.show()
+            .map_err(|error| format!("Failed to run gnuplot: {}", error))?;
+        // Closing waits for gnuplot to finish writing the file
+        fg.close();
+        match std::fs::metadata(filename) {
+            Ok(metadata) if metadata.len() > 0 => Ok(()),
+            _ => Err(format!("gnuplot did not write the image '{}'.", filename)),
+        }
+    }
+
+    /// Maximal runs of samples with `|y| <= y_clip`, as `(xs, ys)`, samples outside the clip are dropped.
+    fn clipped_segments(
+        &self,
+        l: f32,
+        r: f32,
+        num_samples: usize,
+        y_clip: f32,
+    ) -> Vec<(Vec<f32>, Vec<f32>)> {
+        let mut segments = vec![];
+        let (mut xs, mut ys) = (vec![], vec![]);
+        for i in 0..num_samples {
+            let x = l + (r - l) * (i as f32 / (num_samples - 1) as f32);
+            let y = self.at(x);
+            if y.abs() <= y_clip {
+                xs.push(x);
+                ys.push(y);
+            } else if !xs.is_empty() {
+                segments.push((xs, ys));
+                xs = vec![];
+                ys = vec![];
+            }
+        }
+        if !xs.is_empty() {
+            segments.push((xs, ys));
+        }
+        segments
+    }
+
+    /// Like `plot`, but lines are broken wherever `|y|` exceeds `y_clip`, so no near vertical lines are drawn across poles.
+    /// - All segments of a polynomial share one color and caption.
+    pub fn plot_with_breaks(
+        polys: &[&Polynomial],
+        l: f32,
+        r: f32,
+        num_samples: usize,
+        y_clip: f32,
+        filename: &str,
+    ) -> Result<(), PlotError> {
         if num_samples < 2 {
-            return Err("Requested less than 2 samples for plotting.");
+            return Err(PlotError::TooFewSamples);
+        }
+        if l >= r {
+            return Err(PlotError::EmptyRange);
+        }
+        if y_clip.is_nan() || y_clip <= 0.0 {
+            return Err(PlotError::NonPositiveClip);
         }
         use gnuplot::*;
+        const COLORS: [&str; 8] = [
+            "#9400d3", "#009e73", "#56b4e9", "#e69f00", "#f0e442", "#0072b2", "#e51e10", "black",
+        ];
         let mut fg = Figure::new();
         let axes = fg.axes2d();
-        for poly in polys.iter() {
-            axes.lines(
-                (0..num_samples).map(|i| l + (r - l) * (i as f32 / (num_samples - 1) as f32)),
-                (0..num_samples)
-                    .map(|i| l + (r - l) * (i as f32 / (num_samples - 1) as f32))
-                    .map(|x| poly.at(x)),
-                &[Caption(&format!("{}", poly)), LineWidth(1.0)],
-            );
+        for (k, poly) in polys.iter().enumerate() {
+            let caption = poly.to_latex();
+            let color = COLORS[k % COLORS.len()];
+            for (i, (xs, ys)) in poly
+                .clipped_segments(l, r, num_samples, y_clip)
+                .into_iter()
+                .enumerate()
+            {
+                let caption = if i == 0 { caption.as_str() } else { "" };
+                axes.lines(xs, ys, &[Caption(caption), LineWidth(1.0), Color(color)]);
+            }
         }
         axes.set_x_label("x", &[])
             .set_y_label("y", &[])
@@ -77,13 +402,64 @@ impl Polynomial {
             .set_x_grid(true)
             .set_y_grid(true)
             .set_title(
-                &format!("plotted from {} to {} with {} samples", l, r, num_samples),
+                &format!(
+                    "plotted from {} to {} with {} samples, clipped at ±{}",
+                    l, r, num_samples, y_clip
+                ),
                 &[],
             );
-        fg.echo_to_file(&format!("{}.gnuplot", filename));
+        fg.echo_to_file(format!("{}.gnuplot", filename));
         Ok(())
     }
 
+    /// Renders the polynomial in `[l, r]` as a `height` lines by `width` characters ascii graph.
+    /// - One sample is taken per column, the y-axis is scaled to the range of the samples.
+    /// - Samples are marked by `*`, the x-axis (if in view) by `-` and the y-axis (if in view) by `|`.
+    pub fn plot_ascii(&self, l: f32, r: f32, width: usize, height: usize) -> String {
+        if width == 0 || height == 0 {
+            return String::new();
+        }
+        let xs = (0..width)
+            .map(|i| match width {
+                1 => l,
+                _ => l + (r - l) * (i as f32 / (width - 1) as f32),
+            })
+            .collect::<Vec<f32>>();
+        let ys = xs.iter().map(|&x| self.at(x)).collect::<Vec<f32>>();
+        let (y_min, y_max) = {
+            let y_min = ys.iter().cloned().fold(f32::INFINITY, f32::min);
+            let y_max = ys.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+            if y_min == y_max {
+                (y_min - 1.0, y_max + 1.0)
+            } else {
+                (y_min, y_max)
+            }
+        };
+        let row_of =
+            |y: f32| ((y_max - y) / (y_max - y_min) * (height - 1) as f32).round() as usize;
+        let mut canvas = vec![vec![' '; width]; height];
+        if y_min <= 0.0 && 0.0 <= y_max {
+            canvas[row_of(0.0)] = vec!['-'; width];
+        }
+        if let Some(column) = xs.iter().position(|&x| x >= 0.0) {
+            if l <= 0.0 && 0.0 <= r {
+                for row in canvas.iter_mut() {
+                    row[column] = if row[column] == '-' { '+' } else { '|' };
+                }
+            }
+        }
+        for (column, &y) in ys.iter().enumerate() {
+            if y.is_finite() {
+                canvas[row_of(y)][column] = '*';
+            }
+        }
+        canvas
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
     pub fn derivative(&self) -> Self {
         let mut derivative_of_self = Self::new();
         for (&power, &coeff) in self.coeff_of_power.iter() {
@@ -94,6 +470,25 @@ impl Polynomial {
         derivative_of_self
     }
 
+    /// `n`-th derivative in one pass, the coefficient of `x^k` gaining the factor `k (k - 1) ... (k - n + 1)`.
+    /// - `n == 0` gives a clone, `n` above the degree gives zero polynomial.
+    pub fn derivative_n(&self, n: usize) -> Self {
+        let mut derivative_of_self = Self::new();
+        for (&power, &coeff) in self.coeff_of_power.iter() {
+            if power >= n {
+                let coeff = (0..n).fold(coeff, |coeff, j| (power - j) as f32 * coeff);
+                derivative_of_self.insert(power - n, coeff);
+            }
+        }
+        derivative_of_self
+    }
+
+    /// Central difference `(p(x + h) - p(x - h)) / 2h` approximation of the derivative at `x`.
+    /// - Error is `O(h^2)`, but too small `h` amplifies `f32` rounding errors.
+    pub fn numerical_derivative(&self, x: f32, h: f32) -> f32 {
+        (self.at(x + h) - self.at(x - h)) / (2.0 * h)
+    }
+
     pub fn integral(&self, c: f32) -> Self {
         let mut derivative_of_self = Self::new();
         for (&power, &coeff) in self.coeff_of_power.iter() {
@@ -161,7 +556,7 @@ impl Polynomial {
     pub fn real_roots(&self, dx: f32) -> Vec<f32> {
         assert!(dx > 0.0, "dx should be positive.");
         // Zero-term polynomial (zero polynomial)
-        if self.coeff_of_power.len() == 0 {
+        if self.is_zero() {
             return vec![];
         }
         // One-term polynomial
@@ -196,132 +591,2205 @@ impl Polynomial {
         roots.extend(negative_roots);
         roots
     }
-}
 
-impl fmt::Display for Polynomial {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let sorted_coeff_of_power = {
-            let mut map = self.coeff_of_power.iter().collect::<Vec<(&usize, &f32)>>();
-            map.sort_by(|a, b| b.0.cmp(a.0));
-            map
+    /// Real roots found by scanning `[-bound, bound]` for sign changes in `dx` steps, `bound` being `root_bound`.
+    /// - Each sign change is refined by bisection, values that are exactly zero on the grid are returned as is.
+    /// - Time complexity = O(bound / dx), roots of even multiplicity and pairs closer than `dx` can be missed.
+    /// - For zero and constant polynomials an empty vec is returned.
+    pub fn real_roots_bounded(&self, dx: f32) -> Vec<f32> {
+        assert!(dx > 0.0, "dx should be positive.");
+        let bound = match self.root_bound() {
+            Some(bound) => bound,
+            None => return vec![],
         };
-        for (&power, &coeff) in sorted_coeff_of_power {
-            if coeff < 0.0 {
-                write!(f, "{}x^{{{}}}", coeff, power)?;
-            } else {
-                write!(f, "+{}x^{{{}}}", coeff, power)?;
+        let steps = (2.0 * bound / dx).ceil() as usize;
+        let mut roots = Vec::new();
+        let (mut prev_x, mut prev_val) = (-bound, self.at(-bound));
+        for i in 1..=steps {
+            let x = (-bound + i as f32 * dx).min(bound);
+            let val = self.at(x);
+            if val == 0.0 {
+                roots.push(x);
+            } else if prev_val != 0.0 && prev_val.signum() != val.signum() {
+                roots.extend(self.root_in_bracket(prev_x, x, 1e-3 * dx));
             }
+            prev_x = x;
+            prev_val = val;
         }
-        write!(f, "")
+        roots
     }
-}
 
-impl<'a, 'b> Add<&'b Polynomial> for &'a Polynomial {
-    type Output = Polynomial;
-
-    fn add(self, other: &'b Polynomial) -> Polynomial {
-        let mut sum = self.clone();
-        for (&power, &coeff) in other.coeff_of_power.iter() {
-            sum.insert(
-                power,
-                match sum.coeff_of_power.get(&power) {
-                    Some(&prev_coeff) => prev_coeff + coeff,
-                    None => coeff,
-                },
-            );
+    /// Least squares fit of the coefficients of powers `0..=degree` against exact `(x, y)` samples.
+    /// - The fit is solved in `f64` and only truncated back to `f32` at the end.
+    /// - For zero polynomial, or when the samples do not determine all coefficients, a clone of `self` is returned.
+    pub fn refine_all_coefficients_against(&self, samples: &[(f64, f64)]) -> Polynomial {
+        let degree = match self.degree() {
+            Some(degree) => degree,
+            None => return self.clone(),
+        };
+        let vandermonde = samples
+            .iter()
+            .map(|&(x, _)| (0..=degree).map(|power| x.powi(power as i32)).collect())
+            .collect::<Vec<Vec<f64>>>();
+        let ys = samples.iter().map(|&(_, y)| y).collect::<Vec<f64>>();
+        match linalg::least_squares(&vandermonde, &ys) {
+            Some(coeffs) => Polynomial::from_dense_f64(&coeffs),
+            None => self.clone(),
         }
-        sum
     }
-}
 
-impl<'b> AddAssign<&'b Polynomial> for Polynomial {
-    fn add_assign(&mut self, other: &'b Polynomial) {
-        for (&power, &coeff) in other.coeff_of_power.iter() {
-            self.insert(
-                power,
-                match self.coeff_of_power.get(&power) {
-                    Some(&prev_coeff) => prev_coeff + coeff,
-                    None => coeff,
-                },
-            );
-        }
+    /// Formats each coefficient to `decimals` decimal places, e.g. `x^{2} - 5.00x + 6.00`.
+    /// - Terms whose coefficient rounds to zero are omitted, zero polynomial is formatted as `0`.
+    pub fn display_with_precision(&self, decimals: usize) -> String {
+        self.format_terms('x', Some(decimals), true)
     }
-}
 
-impl<'a, 'b> Sub<&'b Polynomial> for &'a Polynomial {
-    type Output = Polynomial;
+    /// Remainder of division by `other`, with degree strictly less than the divisor's.
+    /// - Leading terms whose coefficient magnitude is at most `tol` are trimmed.
+    pub fn rem_clean(&self, other: &Polynomial, tol: f32) -> Polynomial {
+        self.div_rem_clean(other, tol).1
+    }
 
-    fn sub(self, other: &'b Polynomial) -> Polynomial {
-        let mut difference = self.clone();
-        for (&power, &coeff) in other.coeff_of_power.iter() {
-            difference.insert(
-                power,
-                match difference.coeff_of_power.get(&power) {
-                    Some(&prev_coeff) => prev_coeff - coeff,
-                    None => -coeff,
-                },
-            );
+    /// Quotient and remainder of a single long division by `other`, the remainder trimmed as in `rem_clean`.
+    pub fn div_rem_clean(&self, other: &Polynomial, tol: f32) -> (Polynomial, Polynomial) {
+        let (quotient, mut remainder) = self.div_rem(other);
+        while let Some((power, coeff)) = remainder.leading_term() {
+            if coeff.abs() > tol {
+                break;
+            }
+            remainder.coeff_of_power.remove(&power);
         }
-        difference
+        (quotient, remainder)
     }
-}
 
-impl<'b> SubAssign<&'b Polynomial> for Polynomial {
-    fn sub_assign(&mut self, other: &'b Polynomial) {
-        for (&power, &coeff) in other.coeff_of_power.iter() {
-            self.insert(
-                power,
-                match self.coeff_of_power.get(&power) {
-                    Some(&prev_coeff) => prev_coeff - coeff,
-                    None => -coeff,
-                },
-            );
+    /// Monic greatest common divisor by the euclidean algorithm.
+    /// - Remainders are trimmed by `rem_clean` with a tolerance of `1e-5` times the largest input coefficient magnitude,
+    ///   so common factors survive `f32` rounding.
+    /// - `gcd(0, p)` is `p` made monic, `gcd(0, 0)` is the zero polynomial, coprime inputs give `1`.
+    pub fn gcd(&self, other: &Polynomial) -> Polynomial {
+        let tol = 1e-5
+            * self
+                .terms()
+                .chain(other.terms())
+                .fold(0f32, |max, (_, coeff)| max.max(coeff.abs()));
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let remainder = a.rem_clean(&b, tol);
+            a = b;
+            b = remainder;
+        }
+        match a.leading_coefficient() {
+            Some(lead) => &a * (1.0 / lead),
+            None => a,
         }
     }
-}
 
-impl<'a, 'b> Mul<&'b Polynomial> for &'a Polynomial {
-    type Output = Polynomial;
+    /// `self / gcd(self, self')`, removing repeated factors so every root is simple.
+    /// - Same roots as `self`, leading coefficient is kept, zero polynomial stays zero.
+    pub fn square_free(&self) -> Polynomial {
+        if self.is_zero() {
+            return Polynomial::new();
+        }
+        self / &self.gcd(&self.derivative())
+    }
 
-    fn mul(self, other: &'b Polynomial) -> Polynomial {
-        let mut product = Polynomial::new();
-        for (&a_power, &a_coeff) in self.coeff_of_power.iter() {
-            let mut term_mul = Polynomial::new();
-            // Since any term * b will result in non-overlapping terms, simple insert can be used instead of repeated polynomial addition
-            for (&b_power, &b_coeff) in other.coeff_of_power.iter() {
-                term_mul.insert(a_power + b_power, a_coeff * b_coeff);
-            }
-            // Here there can be overlaps and hence polynomial addition is required
-            product += &term_mul;
+    /// `num_samples` logarithmically spaced points from `l` to `r` (inclusive) and the values at them.
+    /// - Requires `0 < l < r`, consecutive points have the constant ratio `(r / l)^(1 / (num_samples - 1))`.
+    pub fn sample_log_grid(
+        &self,
+        l: f32,
+        r: f32,
+        num_samples: usize,
+    ) -> Result<(Vec<f32>, Vec<f32>), SampleError> {
+        if num_samples < 2 {
+            return Err(SampleError::TooFewSamples);
         }
-        product
+        if l <= 0.0 {
+            return Err(SampleError::NonPositiveBound);
+        }
+        if l >= r {
+            return Err(SampleError::EmptyRange);
+        }
+        let (log_l, log_r) = (l.ln(), r.ln());
+        let xs = (0..num_samples)
+            .map(|i| (log_l + (log_r - log_l) * (i as f32 / (num_samples - 1) as f32)).exp())
+            .collect::<Vec<f32>>();
+        let ys = self.evaluate_all(&xs);
+        Ok((xs, ys))
     }
-}
 
-impl<'a, 'b> Div<&'b Polynomial> for &'a Polynomial {
-    type Output = Polynomial;
+    /// `num_samples` evenly spaced points from `l` to `r` (inclusive) and the slopes `p'(x)` at them.
+    pub fn sample_derivative_grid(
+        &self,
+        l: f32,
+        r: f32,
+        num_samples: usize,
+    ) -> Result<(Vec<f32>, Vec<f32>), SampleError> {
+        if num_samples < 2 {
+            return Err(SampleError::TooFewSamples);
+        }
+        if l >= r {
+            return Err(SampleError::EmptyRange);
+        }
+        let xs = (0..num_samples)
+            .map(|i| l + (r - l) * (i as f32 / (num_samples - 1) as f32))
+            .collect::<Vec<f32>>();
+        let slopes = xs.iter().map(|&x| self.derivative_at(x)).collect();
+        Ok((xs, slopes))
+    }
 
-    fn div(self, divisor: &'b Polynomial) -> Polynomial {
-        let divisor_degree = divisor
+    /// Partial quotients `[a0, a1, ...]` of `self / denominator = a0 + 1 / (a1 + 1 / (a2 + ...))`.
+    /// - The quotients are those of the euclidean algorithm on `self` and `denominator`.
+    /// - Remainders that are zero up to `f32` rounding end the expansion.
+    pub fn continued_fraction(&self, denominator: &Polynomial) -> Vec<Polynomial> {
+        denominator
             .degree()
             .expect("Requested division with zero polynomial.");
-        let dividend_degree_opt = self.degree();
-        if let None = dividend_degree_opt {
-            return Polynomial::new();
+        let mut quotients = vec![];
+        let mut dividend = self.clone();
+        let mut divisor = denominator.clone();
+        while !divisor.is_zero() {
+            let tol = dividend.max_norm() * f32::EPSILON * 16.0;
+            let (quotient, remainder) = dividend.div_rem_clean(&divisor, tol);
+            quotients.push(quotient);
+            dividend = divisor;
+            divisor = remainder;
         }
-        let dividend_degree = dividend_degree_opt.unwrap();
-        if dividend_degree < divisor_degree {
-            return Polynomial::new();
+        quotients
+    }
+
+    /// `[m/n]` Padé approximant `(p, q)` of the truncated power series `series`.
+    /// - `p / q` agrees with `series` up to and including `x^(m + n)`, `deg p <= m`, `deg q <= n` and `q(0) = 1`.
+    pub fn pade(
+        series: &Polynomial,
+        m: usize,
+        n: usize,
+    ) -> Result<(Polynomial, Polynomial), PadeError> {
+        let c = |power: isize| -> f64 {
+            if power < 0 {
+                return 0.0;
+            }
+            series
+                .coeff_of_power
+                .get(&(power as usize))
+                .map_or(0.0, |&coeff| coeff as f64)
+        };
+        let (m, n) = (m as isize, n as isize);
+        let system = (m + 1..=m + n)
+            .map(|k| (1..=n).map(|j| c(k - j)).collect())
+            .collect::<Vec<Vec<f64>>>();
+        let rhs = (m + 1..=m + n).map(|k| -c(k)).collect::<Vec<f64>>();
+        let q_coeffs = {
+            let mut q_coeffs = vec![1.0];
+            q_coeffs.extend(linalg::solve(&system, &rhs).ok_or(PadeError::Singular)?);
+            q_coeffs
+        };
+        let mut p = Polynomial::new();
+        for k in 0..=m {
+            let p_k = (0..=k.min(n))
+                .map(|j| q_coeffs[j as usize] * c(k - j))
+                .sum::<f64>();
+            p.insert(k as usize, p_k as f32);
         }
-        let dividend_degree_coeff = self.coeff_of_power.get(&dividend_degree).unwrap();
-        let divisor_degree_coeff = divisor.coeff_of_power.get(&divisor_degree).unwrap();
-        let quotient = polynomial! { dividend_degree - divisor_degree => dividend_degree_coeff / divisor_degree_coeff };
-        let remaining_dividend = {
-            let mut remaining_dividend = self - &(&quotient * &divisor);
-            remaining_dividend.coeff_of_power.remove(&dividend_degree);
-            remaining_dividend
+        Ok((p, Polynomial::from_dense_f64(&q_coeffs)))
+    }
+
+    /// Product `self * kernel` computed by overlap-add block convolution.
+    /// - `self` is split into chunks of `block_size` consecutive powers, each chunk is convolved with the dense `kernel` and added at its offset.
+    /// - Cache friendly alternative to `Mul` for a long signal times a short kernel.
+    pub fn convolve_blocked(&self, kernel: &Polynomial, block_size: usize) -> Polynomial {
+        assert!(block_size > 0, "block_size should be positive.");
+        let (signal_degree, kernel_degree) = match (self.degree(), kernel.degree()) {
+            (Some(signal_degree), Some(kernel_degree)) => (signal_degree, kernel_degree),
+            _ => return Polynomial::new(),
+        };
+        let dense = |poly: &Polynomial, degree: usize| {
+            let mut dense = vec![0f32; degree + 1];
+            for (&power, &coeff) in poly.coeff_of_power.iter() {
+                dense[power] = coeff;
+            }
+            dense
         };
-        &quotient + &(&remaining_dividend / divisor)
+        let signal = dense(self, signal_degree);
+        let kernel = dense(kernel, kernel_degree);
+        let mut output = vec![0f32; signal_degree + kernel_degree + 1];
+        for (block_index, block) in signal.chunks(block_size).enumerate() {
+            let offset = block_index * block_size;
+            let overlap = &mut output[offset..offset + block.len() + kernel_degree];
+            for (i, &signal_coeff) in block.iter().enumerate() {
+                if signal_coeff == 0.0 {
+                    continue;
+                }
+                for (accumulator, &kernel_coeff) in overlap[i..].iter_mut().zip(kernel.iter()) {
+                    *accumulator += signal_coeff * kernel_coeff;
+                }
+            }
+        }
+        let mut product = Polynomial::new();
+        for (power, &coeff) in output.iter().enumerate() {
+            product.insert(power, coeff);
+        }
+        product
+    }
+
+    /// Square root of the sum of squared coefficients.
+    pub fn l2_norm(&self) -> f32 {
+        self.coeff_of_power
+            .values()
+            .map(|coeff| coeff * coeff)
+            .sum::<f32>()
+            .sqrt()
+    }
+
+    /// Sum of absolute values of coefficients.
+    pub fn l1_norm(&self) -> f32 {
+        self.coeff_of_power.values().map(|coeff| coeff.abs()).sum()
+    }
+
+    /// Largest absolute value of coefficients, `0.0` for zero polynomial.
+    pub fn max_norm(&self) -> f32 {
+        self.coeff_of_power
+            .values()
+            .fold(0f32, |max, coeff| max.max(coeff.abs()))
+    }
+
+    /// Scaled copy whose coefficients have unit `l2_norm`, `None` for zero polynomial.
+    pub fn normalize_l2(&self) -> Option<Polynomial> {
+        let norm = self.l2_norm();
+        if norm == 0.0 {
+            return None;
+        }
+        let mut normalized = Polynomial::new();
+        for (&power, &coeff) in self.coeff_of_power.iter() {
+            normalized.insert(power, coeff / norm);
+        }
+        Some(normalized)
+    }
+
+    /// Copy with each coefficient multiplied by `1 + relative * noise`, `noise` uniform in `[-1, 1]`.
+    /// - Useful to study how sensitive roots are to coefficient errors.
+    #[cfg(feature = "rand")]
+    pub fn perturb(&self, relative: f32, rng: &mut impl rand::Rng) -> Polynomial {
+        let mut perturbed = Polynomial::new();
+        for (&power, &coeff) in self.coeff_of_power.iter() {
+            let noise = rng.gen_range(-1.0f32..=1.0);
+            perturbed.insert(power, coeff * (1.0 + relative * noise));
+        }
+        perturbed
+    }
+
+    /// Sum of all (complex) roots counted with multiplicity, `-a_{n-1} / a_n` by Vieta's formulas.
+    /// - `None` for zero and constant polynomials.
+    pub fn sum_of_roots(&self) -> Option<f32> {
+        match self.leading_term() {
+            Some((degree, leading_coeff)) if degree > 0 => {
+                let coeff = self.coeff_of_power.get(&(degree - 1)).unwrap_or(&0.0);
+                Some(-coeff / leading_coeff)
+            }
+            _ => None,
+        }
+    }
+
+    /// Product of all (complex) roots counted with multiplicity, `(-1)^n a_0 / a_n` by Vieta's formulas.
+    /// - `None` for zero and constant polynomials.
+    pub fn product_of_roots(&self) -> Option<f32> {
+        match self.leading_term() {
+            Some((degree, leading_coeff)) if degree > 0 => {
+                let coeff = self.coeff_of_power.get(&0).unwrap_or(&0.0);
+                let sign = if degree % 2 == 0 { 1.0 } else { -1.0 };
+                Some(sign * coeff / leading_coeff)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether all terms have even powers, i.e. `p(-x) = p(x)`. Zero polynomial is even.
+    pub fn is_even(&self) -> bool {
+        self.coeff_of_power.keys().all(|power| power % 2 == 0)
+    }
+
+    /// Whether all terms have odd powers, i.e. `p(-x) = -p(x)`. Zero polynomial is odd.
+    pub fn is_odd(&self) -> bool {
+        self.coeff_of_power.keys().all(|power| power % 2 == 1)
+    }
+
+    /// Stored terms `(power, coeff)`, in ascending order of power.
+    pub fn terms(&self) -> impl Iterator<Item = (usize, f32)> + '_ {
+        self.coeff_of_power
+            .iter()
+            .map(|(&power, &coeff)| (power, coeff))
+    }
+
+    /// Stored terms `(power, coeff)` in descending order of power.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (usize, f32)> + '_ {
+        self.coeff_of_power
+            .iter()
+            .rev()
+            .map(|(&power, &coeff)| (power, coeff))
+    }
+
+    /// `(even(u), odd(u))` for `p(x) = even(x^2) + x odd(x^2)`, by Horner's method in `u` for each parity in one pass over the terms.
+    /// - Gaps between sparse powers of the same parity are bridged by multiplying with `u^(gap / 2)`.
+    fn parity_parts_at(&self, u: f32) -> (f32, f32) {
+        let (mut even, mut odd) = (0f32, 0f32);
+        // Half the power of the last term folded into each part
+        let (mut even_half_power, mut odd_half_power) = (None, None);
+        for (&power, &coeff) in self.coeff_of_power.iter().rev() {
+            let half_power = power / 2;
+            if power % 2 == 0 {
+                if let Some(prev_half_power) = even_half_power {
+                    even *= u.powi((prev_half_power - half_power) as i32);
+                }
+                even += coeff;
+                even_half_power = Some(half_power);
+            } else {
+                if let Some(prev_half_power) = odd_half_power {
+                    odd *= u.powi((prev_half_power - half_power) as i32);
+                }
+                odd += coeff;
+                odd_half_power = Some(half_power);
+            }
+        }
+        (
+            even * u.powi(even_half_power.unwrap_or(0) as i32),
+            odd * u.powi(odd_half_power.unwrap_or(0) as i32),
+        )
+    }
+
+    /// Value at `x` as `even(x^2) + x odd(x^2)`, in one allocation-free pass over the terms in `u = x^2`.
+    /// - For even and odd polynomials consecutive terms are bridged with `u` instead of `x^2`, which makes it somewhat cheaper than `at`.
+    pub fn at_symmetric(&self, x: f32) -> f32 {
+        let (even, odd) = self.parity_parts_at(x * x);
+        even + x * odd
+    }
+
+    /// Natural cubic spline through `points`, one cubic piece per interval between consecutive x-coordinates.
+    /// - Requires at least 2 points with strictly increasing x-coordinates.
+    /// - Second derivative is zero at both ends, value, slope and second derivative are continuous at interior knots.
+    pub fn natural_cubic_spline(points: &[(f32, f32)]) -> Result<PiecewisePolynomial, InterpError> {
+        if points.len() < 2 {
+            return Err(InterpError::TooFewPoints);
+        }
+        if points.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return Err(InterpError::NotStrictlyIncreasing);
+        }
+        let xs = points.iter().map(|&(x, _)| x as f64).collect::<Vec<f64>>();
+        let ys = points.iter().map(|&(_, y)| y as f64).collect::<Vec<f64>>();
+        let n = points.len() - 1;
+        let h = xs
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect::<Vec<f64>>();
+        // Second derivatives at knots, zero at both ends, tridiagonal system solved by thomas algorithm
+        let mut second_derivatives = vec![0f64; n + 1];
+        if n > 1 {
+            let mut upper = vec![0f64; n];
+            let mut rhs = vec![0f64; n];
+            for i in 1..n {
+                let lower = h[i - 1];
+                let diagonal = 2.0 * (h[i - 1] + h[i]);
+                let slope_change =
+                    6.0 * ((ys[i + 1] - ys[i]) / h[i] - (ys[i] - ys[i - 1]) / h[i - 1]);
+                let pivot = diagonal - lower * upper[i - 1];
+                upper[i] = h[i] / pivot;
+                rhs[i] = (slope_change - lower * rhs[i - 1]) / pivot;
+            }
+            for i in (1..n).rev() {
+                second_derivatives[i] = rhs[i] - upper[i] * second_derivatives[i + 1];
+            }
+        }
+        let pieces = (0..n)
+            .map(|i| {
+                let (m0, m1) = (second_derivatives[i], second_derivatives[i + 1]);
+                let taylor_coeffs = [
+                    ys[i],
+                    (ys[i + 1] - ys[i]) / h[i] - h[i] * (2.0 * m0 + m1) / 6.0,
+                    m0 / 2.0,
+                    (m1 - m0) / (6.0 * h[i]),
+                ];
+                Polynomial::from_taylor_coeffs(xs[i], &taylor_coeffs)
+            })
+            .collect();
+        Ok(PiecewisePolynomial {
+            breakpoints: points.iter().map(|&(x, _)| x).collect(),
+            pieces,
+        })
+    }
+
+    /// Expands `sum_k taylor_coeffs[k] * (x - center)^k` in `f64` before truncating to `f32`.
+    fn from_taylor_coeffs(center: f64, taylor_coeffs: &[f64]) -> Polynomial {
+        let mut coeffs = vec![0f64; taylor_coeffs.len()];
+        // binomial[j] = C(k, j) (-center)^(k - j) for the current k
+        let mut binomial = Vec::with_capacity(taylor_coeffs.len());
+        for (k, &taylor_coeff) in taylor_coeffs.iter().enumerate() {
+            binomial.push(1.0);
+            for j in (1..k).rev() {
+                binomial[j] = binomial[j - 1] - center * binomial[j];
+            }
+            if k > 0 {
+                binomial[0] *= -center;
+            }
+            for (coeff, &b) in coeffs.iter_mut().zip(binomial.iter()) {
+                *coeff += taylor_coeff * b;
+            }
+        }
+        Polynomial::from_dense_f64(&coeffs)
+    }
+
+    /// Polynomial with `coeffs[power]` truncated to `f32` as the coefficient of `x^power`.
+    fn from_dense_f64(coeffs: &[f64]) -> Polynomial {
+        let mut poly = Polynomial::new();
+        for (power, &coeff) in coeffs.iter().enumerate() {
+            poly.insert(power, coeff as f32);
+        }
+        poly
+    }
+
+    /// Lagrange interpolating polynomial of degree below `points.len()` through each `(x, y)`.
+    /// - Sum of `y_i prod_{j != i} (x - x_j) / (x_i - x_j)`, expanded in `f64`.
+    /// - Requires at least 1 point and distinct x-coordinates (in any order).
+    pub fn interpolate(points: &[(f32, f32)]) -> Result<Polynomial, &'static str> {
+        if points.is_empty() {
+            return Err("Requested interpolation through no points.");
+        }
+        for (i, &(xi, _)) in points.iter().enumerate() {
+            if points[..i].iter().any(|&(xj, _)| xj == xi) {
+                return Err("Requested interpolation through points with duplicate x-coordinates.");
+            }
+        }
+        let mut coeffs = vec![0f64; points.len()];
+        for (i, &(xi, yi)) in points.iter().enumerate() {
+            let mut basis = vec![1f64];
+            let mut denominator = 1f64;
+            for (j, &(xj, _)) in points.iter().enumerate() {
+                if j == i {
+                    continue;
+                }
+                // basis *= (x - xj)
+                basis.push(0.0);
+                for k in (0..basis.len()).rev() {
+                    let shifted = if k > 0 { basis[k - 1] } else { 0.0 };
+                    basis[k] = shifted - xj as f64 * basis[k];
+                }
+                denominator *= xi as f64 - xj as f64;
+            }
+            for (coeff, &b) in coeffs.iter_mut().zip(basis.iter()) {
+                *coeff += yi as f64 * b / denominator;
+            }
+        }
+        Ok(Polynomial::from_dense_f64(&coeffs))
+    }
+
+    /// Hermite interpolating polynomial matching value and slope at each `(x, f(x), f'(x))`.
+    /// - Built from divided differences on doubled nodes, so its degree is below `2 * points.len()`.
+    /// - Requires at least 1 point and distinct x-coordinates (in any order).
+    pub fn hermite(points: &[(f32, f32, f32)]) -> Result<Polynomial, InterpError> {
+        if points.is_empty() {
+            return Err(InterpError::TooFewPoints);
+        }
+        for (i, &(xi, _, _)) in points.iter().enumerate() {
+            if points[..i].iter().any(|&(xj, _, _)| xj == xi) {
+                return Err(InterpError::DuplicateX);
+            }
+        }
+        let nodes = points
+            .iter()
+            .flat_map(|&(x, _, _)| vec![x as f64, x as f64])
+            .collect::<Vec<f64>>();
+        // Divided differences, updated in place column by column
+        let mut differences = points
+            .iter()
+            .flat_map(|&(_, f, _)| vec![f as f64, f as f64])
+            .collect::<Vec<f64>>();
+        let mut newton_coeffs = vec![differences[0]];
+        for order in 1..nodes.len() {
+            for i in (order..nodes.len()).rev() {
+                differences[i] = if order == 1 && i % 2 == 1 {
+                    points[i / 2].2 as f64
+                } else {
+                    (differences[i] - differences[i - 1]) / (nodes[i] - nodes[i - order])
+                };
+            }
+            newton_coeffs.push(differences[order]);
+        }
+        // Expand sum_k newton_coeffs[k] * prod_{j < k} (x - nodes[j])
+        let mut coeffs = vec![0f64; nodes.len()];
+        let mut basis = vec![1f64];
+        for (k, &newton_coeff) in newton_coeffs.iter().enumerate() {
+            for (coeff, &b) in coeffs.iter_mut().zip(basis.iter()) {
+                *coeff += newton_coeff * b;
+            }
+            basis.push(0.0);
+            for j in (0..basis.len()).rev() {
+                let shifted = if j > 0 { basis[j - 1] } else { 0.0 };
+                basis[j] = shifted - nodes[k] * basis[j];
+            }
+        }
+        Ok(Polynomial::from_dense_f64(&coeffs))
+    }
+
+    /// Orthogonal basis of polynomials of degree at most `degree` under `<p, q> = ∫_a^b w(x) p(x) q(x) dx`.
+    /// - Gram-Schmidt orthogonalization of `1, x, ..., x^degree`, the `k`th element is monic of degree `k`.
+    /// - Integrals are approximated by composite Simpson's rule with about `quad_points` samples.
+    /// - With `w = 1` on `[-1, 1]` this gives the Legendre polynomials up to scaling.
+    pub fn orthogonal_basis(
+        degree: usize,
+        weight: impl Fn(f32) -> f32,
+        a: f32,
+        b: f32,
+        quad_points: usize,
+    ) -> Vec<Polynomial> {
+        let mut basis: Vec<Polynomial> = Vec::with_capacity(degree + 1);
+        for power in 0..=degree {
+            let mut orthogonalized = Polynomial::monomial(power, 1.0);
+            for element in basis.iter() {
+                let projection =
+                    Polynomial::inner_product(&orthogonalized, element, &weight, a, b, quad_points)
+                        / Polynomial::inner_product(element, element, &weight, a, b, quad_points);
+                orthogonalized -= &(element * &Polynomial::monomial(0, projection));
+            }
+            basis.push(orthogonalized);
+        }
+        basis
+    }
+
+    /// Composite Simpson's rule approximation of `∫_a^b w(x) p(x) q(x) dx` with about `quad_points` samples.
+    fn inner_product(
+        p: &Polynomial,
+        q: &Polynomial,
+        weight: &impl Fn(f32) -> f32,
+        a: f32,
+        b: f32,
+        quad_points: usize,
+    ) -> f32 {
+        // Simpson's rule needs an even number of intervals
+        let intervals = quad_points.saturating_sub(1).max(2);
+        let intervals = intervals + intervals % 2;
+        let h = (b - a) as f64 / intervals as f64;
+        let sum = (0..=intervals)
+            .map(|i| {
+                let x = (a as f64 + h * i as f64) as f32;
+                let factor = match i {
+                    0 => 1.0,
+                    i if i == intervals => 1.0,
+                    i if i % 2 == 1 => 4.0,
+                    _ => 2.0,
+                };
+                factor * weight(x) as f64 * p.at(x) as f64 * q.at(x) as f64
+            })
+            .sum::<f64>();
+        (sum * h / 3.0) as f32
+    }
+
+    /// Coefficients `c` of `self ≈ sum_i c[i] * basis[i]` for an orthogonal `basis`, e.g. from `orthogonal_basis`.
+    /// - `c[i] = <self, basis[i]> / <basis[i], basis[i]>` under the same weighted inner product.
+    pub fn project(
+        &self,
+        basis: &[Polynomial],
+        weight: impl Fn(f32) -> f32,
+        a: f32,
+        b: f32,
+        quad_points: usize,
+    ) -> Vec<f32> {
+        basis
+            .iter()
+            .map(|element| {
+                Polynomial::inner_product(self, element, &weight, a, b, quad_points)
+                    / Polynomial::inner_product(element, element, &weight, a, b, quad_points)
+            })
+            .collect()
+    }
+
+    /// Degree `degree` polynomial minimizing the maximum error against `target` on `[a, b]`, by Remez exchange.
+    /// - Starts from Chebyshev extrema and runs at most `max_iter` exchanges, stopping early once the error equioscillates.
+    /// - Computed in `f64` on `[a, b]` mapped to `[-1, 1]`, only the result is truncated to `f32`.
+    pub fn remez(
+        target: impl Fn(f64) -> f64,
+        degree: usize,
+        a: f64,
+        b: f64,
+        max_iter: usize,
+    ) -> Polynomial {
+        assert!(a < b, "Left bound must be strictly less than right bound.");
+        let (mid, half) = ((a + b) / 2.0, (b - a) / 2.0);
+        let f = |t: f64| target(mid + half * t);
+        let eval = |coeffs: &[f64], t: f64| coeffs.iter().rev().fold(0.0, |acc, &c| acc * t + c);
+        let num_refs = degree + 2;
+        let mut references = (0..num_refs)
+            .map(|i| -(std::f64::consts::PI * i as f64 / (num_refs - 1) as f64).cos())
+            .collect::<Vec<f64>>();
+        let mut coeffs = vec![0.0; degree + 1];
+        for _ in 0..max_iter.max(1) {
+            // Solve p(t_i) + (-1)^i E = f(t_i) for the coefficients of p and the levelled error E
+            let system = references
+                .iter()
+                .enumerate()
+                .map(|(i, &t)| {
+                    let mut row = (0..=degree).map(|k| t.powi(k as i32)).collect::<Vec<f64>>();
+                    row.push(if i % 2 == 0 { 1.0 } else { -1.0 });
+                    row
+                })
+                .collect::<Vec<Vec<f64>>>();
+            let rhs = references.iter().map(|&t| f(t)).collect::<Vec<f64>>();
+            match linalg::solve(&system, &rhs) {
+                Some(solution) => coeffs = solution[..=degree].to_vec(),
+                None => break,
+            }
+            let error = |t: f64| f(t) - eval(&coeffs, t);
+            // Zeros of the error between consecutive references split [-1, 1] into segments of constant sign
+            let mut boundaries = vec![-1.0];
+            for pair in references.windows(2) {
+                let (mut lo, mut hi) = (pair[0], pair[1]);
+                if error(lo) * error(hi) < 0.0 {
+                    for _ in 0..100 {
+                        let mid = (lo + hi) / 2.0;
+                        if error(lo) * error(mid) <= 0.0 {
+                            hi = mid;
+                        } else {
+                            lo = mid;
+                        }
+                    }
+                }
+                boundaries.push((lo + hi) / 2.0);
+            }
+            boundaries.push(1.0);
+            // The new reference is the extremum of the error in each segment
+            let new_references = boundaries
+                .windows(2)
+                .map(|segment| {
+                    let samples = 64;
+                    let step = (segment[1] - segment[0]) / samples as f64;
+                    let best = (0..=samples)
+                        .map(|i| segment[0] + step * i as f64)
+                        .max_by(|&s, &t| error(s).abs().total_cmp(&error(t).abs()))
+                        .unwrap();
+                    // Golden section refinement around the best sample
+                    let (mut lo, mut hi) =
+                        ((best - step).max(segment[0]), (best + step).min(segment[1]));
+                    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+                    for _ in 0..60 {
+                        let s = hi - ratio * (hi - lo);
+                        let t = lo + ratio * (hi - lo);
+                        if error(s).abs() > error(t).abs() {
+                            hi = t;
+                        } else {
+                            lo = s;
+                        }
+                    }
+                    let refined = (lo + hi) / 2.0;
+                    if error(refined).abs() >= error(best).abs() {
+                        refined
+                    } else {
+                        best
+                    }
+                })
+                .collect::<Vec<f64>>();
+            let magnitudes = new_references
+                .iter()
+                .map(|&t| error(t).abs())
+                .collect::<Vec<f64>>();
+            let max = magnitudes.iter().cloned().fold(0.0, f64::max);
+            let min = magnitudes.iter().cloned().fold(f64::INFINITY, f64::min);
+            references = new_references;
+            if max - min <= 1e-9 * max.max(f64::MIN_POSITIVE) {
+                break;
+            }
+        }
+        let taylor_coeffs = coeffs
+            .iter()
+            .enumerate()
+            .map(|(k, &c)| c / half.powi(k as i32))
+            .collect::<Vec<f64>>();
+        Polynomial::from_taylor_coeffs(mid, &taylor_coeffs)
+    }
+
+    /// Coefficients in `f64`, `dense[power]` being the coefficient of `x^power`, empty for zero polynomial.
+    fn to_dense_f64(&self) -> Vec<f64> {
+        let mut dense = vec![0f64; self.degree().map_or(0, |degree| degree + 1)];
+        for (&power, &coeff) in self.coeff_of_power.iter() {
+            dense[power] = coeff as f64;
+        }
+        dense
+    }
+
+    /// Coefficients `dense[power]` of `x^power` for powers `0..=degree`, empty for zero polynomial.
+    pub fn to_dense(&self) -> Vec<f32> {
+        let mut dense = vec![0f32; self.degree().map_or(0, |degree| degree + 1)];
+        for (&power, &coeff) in self.coeff_of_power.iter() {
+            dense[power] = coeff;
+        }
+        dense
+    }
+
+    /// Disjoint intervals `(lo, hi)` in ascending order, each containing exactly one distinct real root.
+    /// - Vincent–Akritas–Strzeboński continued fraction method on the square-free part, in `f64`.
+    /// - Roots found exactly (e.g. `0`) are returned as degenerate intervals `(root, root)`.
+    /// - `None` if the subdivision does not finish, e.g. for NaN coefficients.
+    /// - For zero polynomial an empty vec is returned.
+    pub fn isolate_real_roots(&self) -> Option<Vec<(f32, f32)>> {
+        let p = self.to_dense_f64();
+        let zero_multiplicity = match p.iter().position(|&c| c != 0.0) {
+            Some(zero_multiplicity) => zero_multiplicity,
+            None => return Some(vec![]),
+        };
+        let p = dense::square_free(&p[zero_multiplicity..]);
+        let mut intervals = dense::isolate_positive_roots_vas(&dense::reflect(&p))?
+            .into_iter()
+            .map(|(lo, hi)| (-hi, -lo))
+            .collect::<Vec<(f64, f64)>>();
+        if zero_multiplicity > 0 {
+            intervals.push((0.0, 0.0));
+        }
+        intervals.extend(dense::isolate_positive_roots_vas(&p)?);
+        intervals.sort_by(|s, t| s.0.total_cmp(&t.0));
+        Some(
+            intervals
+                .into_iter()
+                .map(|(lo, hi)| (lo as f32, hi as f32))
+                .collect(),
+        )
+    }
+
+    /// Disjoint intervals `(lo, hi)` in ascending order, each containing exactly one distinct positive root.
+    /// - Same continued fraction method as `isolate_real_roots`, restricted to `x > 0`, and `None` in the same cases.
+    /// - For zero polynomial an empty vec is returned.
+    pub fn isolate_positive_roots(&self) -> Option<Vec<(f32, f32)>> {
+        let p = self.to_dense_f64();
+        let p = match p.iter().position(|&c| c != 0.0) {
+            Some(zero_multiplicity) => dense::square_free(&p[zero_multiplicity..]),
+            None => return Some(vec![]),
+        };
+        Some(
+            dense::isolate_positive_roots_vas(&p)?
+                .into_iter()
+                .map(|(lo, hi)| (lo as f32, hi as f32))
+                .collect(),
+        )
+    }
+
+    /// `self(inner)`, by Horner's method over polynomials.
+    pub fn compose(&self, inner: &Polynomial) -> Polynomial {
+        let degree = match self.degree() {
+            Some(degree) => degree,
+            None => return Polynomial::new(),
+        };
+        let mut result = Polynomial::new();
+        for power in (0..=degree).rev() {
+            result = &result * inner;
+            if let Some(&coeff) = self.coeff_of_power.get(&power) {
+                result.add_term(0, coeff);
+            }
+        }
+        result
+    }
+
+    /// `p(x + a)`, by repeated synthetic division in `f64`.
+    pub fn shift(&self, a: f32) -> Polynomial {
+        Polynomial::from_dense_f64(&dense::taylor_shift(&self.to_dense_f64(), a as f64))
+    }
+
+    /// `p(c x)`, i.e. the coefficient of `x^k` multiplied by `c^k`.
+    /// - For `c == 0` only the constant term remains.
+    pub fn scale_x(&self, c: f32) -> Polynomial {
+        let mut scaled = Polynomial::new();
+        for (&power, &coeff) in self.coeff_of_power.iter() {
+            scaled.insert(power, coeff * c.powi(power as i32));
+        }
+        scaled
+    }
+
+    /// `self(inner) mod modulus`, reducing after each Horner step so degrees stay below that of `modulus`.
+    /// - Panics if `modulus` is zero polynomial.
+    pub fn compose_mod(&self, inner: &Polynomial, modulus: &Polynomial) -> Polynomial {
+        let degree = match self.degree() {
+            Some(degree) => degree,
+            None => return Polynomial::new(),
+        };
+        let inner = inner % modulus;
+        let mut result = Polynomial::new();
+        for power in (0..=degree).rev() {
+            result = &result * &inner;
+            if let Some(&coeff) = self.coeff_of_power.get(&power) {
+                result += &Polynomial::monomial(0, coeff);
+            }
+            result = &result % modulus;
+        }
+        result
+    }
+
+    /// `(self * other) mod modulus`.
+    /// - Panics if `modulus` is zero polynomial.
+    pub fn mul_mod(&self, other: &Polynomial, modulus: &Polynomial) -> Polynomial {
+        &(&(self % modulus) * &(other % modulus)) % modulus
+    }
+
+    /// `self^exp mod modulus` by exponentiation by squaring, reducing after each step.
+    /// - Panics if `modulus` is zero polynomial.
+    pub fn pow_mod(&self, exp: usize, modulus: &Polynomial) -> Polynomial {
+        let mut base = self % modulus;
+        let mut exp = exp;
+        let mut result = &Polynomial::monomial(0, 1.0) % modulus;
+        while exp > 0 {
+            if exp % 2 == 1 {
+                result = result.mul_mod(&base, modulus);
+            }
+            base = base.mul_mod(&base, modulus);
+            exp /= 2;
+        }
+        result
+    }
+
+    /// `n`-th term (from 0) of the linear recurrence with characteristic polynomial `self` and the given first terms.
+    /// - Computes `x^n mod self` with `pow_mod` and applies it to `initial`, in `O(deg^2 log n)`.
+    /// - `None` for zero polynomial or if `initial.len()` is not the degree.
+    pub fn recurrence_nth_term(&self, initial: &[f32], n: usize) -> Option<f32> {
+        if self.degree()? != initial.len() {
+            return None;
+        }
+        let reduced = Polynomial::monomial(1, 1.0).pow_mod(n, self);
+        Some(
+            reduced
+                .coeff_of_power
+                .iter()
+                .map(|(&power, &coeff)| coeff * initial[power])
+                .sum(),
+        )
+    }
+
+    /// Rust source of `fn fn_name(x: f32) -> f32` evaluating this polynomial by Horner's method.
+    /// - Coefficients are emitted as `f32` literals, so they must be finite.
+    pub fn to_rust_fn_source(&self, fn_name: &str) -> String {
+        let degree = match self.degree() {
+            Some(degree) if degree > 0 => degree,
+            _ => {
+                let constant = self.coeff_of_power.get(&0).cloned().unwrap_or(0.0);
+                return format!(
+                    "fn {}(_x: f32) -> f32 {{\n    {:?}f32\n}}\n",
+                    fn_name, constant
+                );
+            }
+        };
+        let coeff = |power| self.coeff_of_power.get(&power).cloned().unwrap_or(0.0);
+        let mut source = format!("fn {}(x: f32) -> f32 {{\n", fn_name);
+        source += &format!("    let mut y = {:?}f32;\n", coeff(degree));
+        for power in (0..degree).rev() {
+            source += &format!("    y = y * x + {:?}f32;\n", coeff(power));
+        }
+        source += "    y\n}\n";
+        source
+    }
+
+    /// Stable 64-bit FNV-1a hash of the terms sorted by power, independent of insertion order.
+    pub fn checksum(&self) -> u64 {
+        let mut terms = self
+            .coeff_of_power
+            .iter()
+            .map(|(&power, &coeff)| (power as u64, coeff.to_bits()))
+            .collect::<Vec<(u64, u32)>>();
+        terms.sort_unstable();
+        let mut hash = 0xcbf29ce484222325u64;
+        for (power, bits) in terms {
+            for byte in power.to_le_bytes().iter().chain(bits.to_le_bytes().iter()) {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        }
+        hash
+    }
+
+    /// `(p(x), p'(x))` in a single Horner pass.
+    pub fn evaluate_with_derivative(&self, x: f32) -> (f32, f32) {
+        let degree = match self.degree() {
+            Some(degree) => degree,
+            None => return (0.0, 0.0),
+        };
+        let (mut value, mut slope) = (0f32, 0f32);
+        for power in (0..=degree).rev() {
+            slope = slope * x + value;
+            value = value * x + self.coeff_of_power.get(&power).cloned().unwrap_or(0.0);
+        }
+        (value, slope)
+    }
+
+    /// `p'(x)` without constructing the derivative polynomial.
+    pub fn derivative_at(&self, x: f32) -> f32 {
+        self.evaluate_with_derivative(x).1
+    }
+
+    /// Newton step `p(x) / p'(x)`, `None` if `p'(x)` is zero.
+    pub fn newton_fraction(&self, x: f32) -> Option<f32> {
+        let (value, slope) = self.evaluate_with_derivative(x);
+        if slope == 0.0 {
+            None
+        } else {
+            Some(value / slope)
+        }
+    }
+
+    /// Root in `[a, b]` by bisection, narrowed until the bracket is at most `tol` wide.
+    /// - `None` unless `self.at(a)` and `self.at(b)` have opposite signs (or one is zero).
+    pub fn root_in_bracket(&self, a: f32, b: f32, tol: f32) -> Option<f32> {
+        let (mut lo, mut hi) = (a.min(b), a.max(b));
+        let (mut value_lo, value_hi) = (self.at(lo), self.at(hi));
+        if value_lo == 0.0 {
+            return Some(lo);
+        }
+        if value_hi == 0.0 {
+            return Some(hi);
+        }
+        if value_lo.signum() == value_hi.signum() {
+            return None;
+        }
+        while hi - lo > tol {
+            let mid = (lo + hi) / 2.0;
+            if mid <= lo || mid >= hi {
+                // Bracket can not be narrowed further in f32
+                break;
+            }
+            let value_mid = self.at(mid);
+            if value_mid == 0.0 {
+                return Some(mid);
+            }
+            if value_mid.signum() == value_lo.signum() {
+                lo = mid;
+                value_lo = value_mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Some((lo + hi) / 2.0)
+    }
+
+    /// `p(x)` and the Newton iterate `x - p(x) / p'(x)` from a single Horner pass.
+    /// - The iterate is `None` if `p'(x)` underflows to zero (or subnormal) or it is not finite.
+    fn newton_step(&self, x: f32) -> (f32, Option<f32>) {
+        let (value, slope) = self.evaluate_with_derivative(x);
+        if slope.abs() < f32::MIN_POSITIVE {
+            return (value, None);
+        }
+        (
+            value,
+            Some(x - value / slope).filter(|next| next.is_finite()),
+        )
+    }
+
+    /// Root from `guess` by plain Newton's method, converged when a step is at most `tol` in size.
+    /// - `None` if the derivative underflows to zero (or subnormal) or it does not converge within `max_iter` steps.
+    pub fn refine_root(&self, guess: f32, max_iter: usize, tol: f32) -> Option<f32> {
+        let mut x = guess;
+        for _ in 0..max_iter {
+            let next = self.newton_step(x).1?;
+            if (next - x).abs() <= tol {
+                return Some(next);
+            }
+            x = next;
+        }
+        None
+    }
+
+    /// Root nearby `guess` by Newton's method, `None` if it does not converge within `max_iter` steps.
+    /// - Once iterates are seen on both sides of a sign change, steps leaving that bracket
+    ///   (or impossible as the derivative vanishes) are replaced by bisection.
+    /// - Converged when a step is at most `tol` in size.
+    pub fn root_near(&self, guess: f32, tol: f32, max_iter: usize) -> Option<f32> {
+        let (mut below, mut above) = (None::<f32>, None::<f32>);
+        let mut x = guess;
+        for _ in 0..max_iter {
+            let (value, newton) = self.newton_step(x);
+            if value == 0.0 {
+                return Some(x);
+            }
+            if value < 0.0 {
+                below = Some(x);
+            } else {
+                above = Some(x);
+            }
+            let next = match (below, above, newton) {
+                (Some(a), Some(b), _) => {
+                    let (lo, hi) = (a.min(b), a.max(b));
+                    match newton {
+                        Some(newton) if lo < newton && newton < hi => newton,
+                        _ => (lo + hi) / 2.0,
+                    }
+                }
+                (_, _, Some(newton)) => newton,
+                _ => return None,
+            };
+            if (next - x).abs() <= tol {
+                return Some(next);
+            }
+            x = next;
+        }
+        None
+    }
+
+    /// Scaled by `1 / |leading coefficient|`, so the leading coefficient becomes `1` or `-1`.
+    /// - `None` for zero polynomial.
+    pub fn unit_leading(&self) -> Option<Polynomial> {
+        let lead = self.leading_coefficient()?;
+        let scale = lead.abs();
+        let mut scaled = Polynomial::new();
+        for (&power, &coeff) in self.coeff_of_power.iter() {
+            scaled.insert(power, coeff / scale);
+        }
+        Some(scaled)
+    }
+
+    /// Estimated degree of the polynomial through equally spaced `points`, sorted by x.
+    /// - Order of the first finite differences whose values spread at most `tol`.
+    /// - `None` if there are less than 2 points, x-coordinates are not equally spaced (up to rounding),
+    ///   or no order with at least 2 differences is constant.
+    pub fn estimate_degree_from_samples(points: &[(f32, f32)], tol: f32) -> Option<usize> {
+        if points.len() < 2 {
+            return None;
+        }
+        if points[1].0 == points[0].0 || !Polynomial::is_equally_spaced(points) {
+            return None;
+        }
+        let mut differences = points.iter().map(|&(_, y)| y).collect::<Vec<f32>>();
+        for order in 0..points.len() - 1 {
+            let (min, max) = differences
+                .iter()
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &d| {
+                    (min.min(d), max.max(d))
+                });
+            if max - min <= tol {
+                return Some(order);
+            }
+            differences = differences.windows(2).map(|w| w[1] - w[0]).collect();
+        }
+        None
+    }
+
+    /// All complex roots in `f64` as eigenvalues of the balanced companion matrix, empty if they do not converge.
+    fn roots_f64(&self) -> Vec<Complex> {
+        let p = self.to_dense_f64();
+        if p.len() < 2 {
+            return vec![];
+        }
+        let mut companion = dense::companion_matrix(&p);
+        linalg::balance(&mut companion);
+        linalg::hessenberg_eigenvalues(&companion).unwrap_or_default()
+    }
+
+    /// Each complex root `(re, im, condition)`, the condition number measuring sensitivity to relative coefficient perturbations.
+    /// - `condition = sum(|a_k| |r|^k) / (|r| |p'(r)|)`, without the `|r|` for a zero root, infinite at multiple roots.
+    /// - Sorted by real then imaginary part, empty for constant polynomials.
+    pub fn root_sensitivity(&self) -> Vec<(f64, f64, f64)> {
+        let p = self.to_dense_f64();
+        let slope = dense::derivative(&p);
+        let mut sensitivities = self
+            .roots_f64()
+            .into_iter()
+            .map(|root| {
+                let modulus = root.norm();
+                let magnitude = p.iter().rev().fold(0.0, |acc, c| acc * modulus + c.abs());
+                let slope_at = dense::at_complex(&slope, root).norm();
+                let condition = if slope_at == 0.0 {
+                    f64::INFINITY
+                } else if modulus == 0.0 {
+                    magnitude / slope_at
+                } else {
+                    magnitude / (modulus * slope_at)
+                };
+                (root.re, root.im, condition)
+            })
+            .collect::<Vec<(f64, f64, f64)>>();
+        sensitivities.sort_by(|s, t| s.0.total_cmp(&t.0).then(s.1.total_cmp(&t.1)));
+        sensitivities
+    }
+
+    /// All complex roots `(re, im)` counted with multiplicity, sorted by real then imaginary part.
+    /// - Eigenvalues of the balanced companion matrix by shifted QR iteration, linear polynomials are solved directly.
+    /// - Each root in the upper half plane is paired with its nearest counterpart in the lower one,
+    ///   and both are replaced by their average so conjugate pairs are exact.
+    /// - Empty for zero and constant polynomials.
+    pub fn complex_roots(&self) -> Vec<(f32, f32)> {
+        let p = self.to_dense_f64();
+        let (upper, mut rest): (Vec<_>, Vec<_>) = match p.len() {
+            0 | 1 => return vec![],
+            2 => (vec![], vec![Complex::new(-p[0] / p[1], 0.0)]),
+            _ => self.roots_f64().into_iter().partition(|root| root.im > 0.0),
+        };
+        let mut roots = vec![];
+        for root in upper {
+            let partner = rest
+                .iter()
+                .enumerate()
+                .filter(|(_, other)| other.im < 0.0)
+                .min_by(|(_, s), (_, t)| {
+                    (root - s.conj())
+                        .norm()
+                        .total_cmp(&(root - t.conj()).norm())
+                })
+                .map(|(i, _)| i);
+            match partner {
+                Some(i) => {
+                    let root = (root + rest.swap_remove(i).conj()).scale(0.5);
+                    roots.push((root.re, root.im));
+                    roots.push((root.re, -root.im));
+                }
+                None => roots.push((root.re, root.im)),
+            }
+        }
+        roots.extend(rest.into_iter().map(|root| (root.re, root.im)));
+        roots.sort_by(|s, t| s.0.total_cmp(&t.0).then(s.1.total_cmp(&t.1)));
+        roots
+            .into_iter()
+            .map(|(re, im)| (re as f32, im as f32))
+            .collect()
+    }
+
+    /// Sum of terms in the variable `var` in decreasing power, e.g. `2s^2 - s + 0.5`, `0` for zero polynomial.
+    /// - Unit coefficients are omitted except on the constant term, powers 1 and 0 omit `var^1` and `var^0`.
+    /// - With `decimals`, coefficients are rounded to that many decimal places and terms rounding to zero are dropped.
+    /// - With `braced`, powers are written `var^{n}` as in LaTeX.
+    fn format_terms(&self, var: char, decimals: Option<usize>, braced: bool) -> String {
+        let mut formatted = String::new();
+        for (power, coeff) in self.iter_sorted() {
+            let magnitude = match decimals {
+                Some(decimals) => format!("{:.*}", decimals, coeff.abs()),
+                None => coeff.abs().to_string(),
+            };
+            if magnitude.chars().all(|c| c == '0' || c == '.') {
+                continue;
+            }
+            match (formatted.is_empty(), coeff < 0.0) {
+                (true, true) => formatted.push('-'),
+                (true, false) => (),
+                (false, true) => formatted.push_str(" - "),
+                (false, false) => formatted.push_str(" + "),
+            }
+            let magnitude = if coeff.abs() == 1.0 && power > 0 {
+                String::new()
+            } else {
+                magnitude
+            };
+            match (power, braced) {
+                (0, _) => formatted.push_str(&magnitude),
+                (1, _) => formatted.push_str(&format!("{}{}", magnitude, var)),
+                (_, true) => formatted.push_str(&format!("{}{}^{{{}}}", magnitude, var, power)),
+                (_, false) => formatted.push_str(&format!("{}{}^{}", magnitude, var, power)),
+            }
+        }
+        if formatted.is_empty() {
+            formatted.push('0');
+        }
+        formatted
+    }
+
+    /// LaTeX form, e.g. `x^{2} - x + 6`, `0` for zero polynomial.
+    pub fn to_latex(&self) -> String {
+        self.format_terms('x', None, true)
+    }
+
+    /// Rational transfer function `num(var) / den(var)` on three lines, e.g. for `1 / (s + 1)`:
+    /// ```text
+    ///   1
+    /// -----
+    /// s + 1
+    /// ```
+    /// - The bar is as long as the longer of numerator and denominator, the shorter one is centered.
+    pub fn to_transfer_function(num: &Polynomial, den: &Polynomial, var: char) -> String {
+        let (num, den) = (
+            num.format_terms(var, None, false),
+            den.format_terms(var, None, false),
+        );
+        let width = num.chars().count().max(den.chars().count());
+        let centered = |line: &str| {
+            let padding = (width - line.chars().count()) / 2;
+            format!("{}{}", " ".repeat(padding), line)
+        };
+        format!(
+            "{}\n{}\n{}",
+            centered(&num),
+            "-".repeat(width),
+            centered(&den)
+        )
+    }
+
+    /// `[p(x), p'(x), p''(x), ...]` up to the degree-th derivative, empty for zero polynomial.
+    /// - Taylor coefficients at `x` by repeated synthetic division in `f64`, scaled by `k!`.
+    /// - Derivatives beyond the range of `f32` (e.g. `k!` times a non-zero `x^k` coefficient for `k >= 35`) are infinite.
+    pub fn all_derivatives_at(&self, x: f32) -> Vec<f32> {
+        let mut factorial = 1f64;
+        dense::taylor_shift(&self.to_dense_f64(), x as f64)
+            .into_iter()
+            .enumerate()
+            .map(|(k, c)| {
+                factorial *= k.max(1) as f64;
+                (c * factorial) as f32
+            })
+            .collect()
+    }
+
+    /// Coefficient of `x^k` recovered as `p^(k)(0) / k!`.
+    /// - Both the derivative and `k!` are computed in `f64`, where `k!` stays finite up to `k = 170` (in `f32` only up to `k = 34`).
+    pub fn coefficient_via_derivative(&self, k: usize) -> f32 {
+        let mut derivative = self.to_dense_f64();
+        for _ in 0..k {
+            derivative = dense::derivative(&derivative);
+        }
+        let factorial = (1..=k).map(|i| i as f64).product::<f64>();
+        match derivative.first() {
+            Some(&derivative_at_zero) => (derivative_at_zero / factorial) as f32,
+            None => 0.0,
+        }
+    }
+
+    /// Companion matrix (as rows) after Parlett–Reinsch diagonal balancing, which has the same eigenvalues but a smaller norm.
+    /// - First row of the unbalanced matrix is `-a[n-1]/a[n], ..., -a[0]/a[n]`, subdiagonal is ones.
+    /// - `None` for polynomials of degree less than 1.
+    pub fn balanced_companion_matrix(&self) -> Option<Vec<Vec<f64>>> {
+        let p = self.to_dense_f64();
+        if p.len() < 2 {
+            return None;
+        }
+        let mut companion = dense::companion_matrix(&p);
+        linalg::balance(&mut companion);
+        Some(companion)
+    }
+
+    /// `Err(DegreeError { actual, max })` if the degree exceeds `max_degree`, zero polynomial always passes.
+    pub fn require_max_degree(&self, max_degree: usize) -> Result<(), DegreeError> {
+        match self.degree() {
+            Some(actual) if actual > max_degree => Err(DegreeError {
+                actual,
+                max: max_degree,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether consecutive x-coordinates of `points` differ by the same amount, up to `f32` rounding.
+    fn is_equally_spaced(points: &[(f32, f32)]) -> bool {
+        if points.len() < 2 {
+            return true;
+        }
+        let spacing = points[1].0 - points[0].0;
+        let scale = points.iter().fold(0f32, |max, &(x, _)| max.max(x.abs()));
+        points
+            .windows(2)
+            .all(|w| ((w[1].0 - w[0].0) - spacing).abs() <= 8.0 * f32::EPSILON * scale)
+    }
+
+    /// Forward difference triangle of the y-values of equally spaced `points`, sorted by x.
+    /// - Row `k` holds the `k`-th differences, so the first element of each row feeds Newton's forward formula.
+    pub fn forward_difference_table(points: &[(f32, f32)]) -> Result<Vec<Vec<f32>>, InterpError> {
+        if points.is_empty() {
+            return Err(InterpError::TooFewPoints);
+        }
+        if points.windows(2).any(|w| w[0].0 >= w[1].0) {
+            return Err(InterpError::NotStrictlyIncreasing);
+        }
+        if !Polynomial::is_equally_spaced(points) {
+            return Err(InterpError::UnequalSpacing);
+        }
+        let mut table = vec![points.iter().map(|&(_, y)| y).collect::<Vec<f32>>()];
+        while table[table.len() - 1].len() > 1 {
+            let next = table[table.len() - 1]
+                .windows(2)
+                .map(|w| w[1] - w[0])
+                .collect();
+            table.push(next);
+        }
+        Ok(table)
+    }
+
+    /// Interpolating polynomial of equally spaced `points` by Newton's forward difference formula.
+    /// - `p(x0 + s h) = sum over k of C(s, k) Δ^k y0`.
+    pub fn newton_forward(points: &[(f32, f32)]) -> Result<Polynomial, InterpError> {
+        let table = Polynomial::forward_difference_table(points)?;
+        let x0 = points[0].0;
+        let h = if points.len() > 1 {
+            points[1].0 - x0
+        } else {
+            1.0
+        };
+        let mut interpolant = Polynomial::new();
+        // C(s, k) as a polynomial in x, with s = (x - x0) / h
+        let mut binomial = polynomial! { 0 => 1.0 };
+        for (k, row) in table.iter().enumerate() {
+            interpolant += &(&binomial * &polynomial! { 0 => row[0] });
+            let factor = polynomial! { 1 => 1.0 / h, 0 => -x0 / h - k as f32 };
+            binomial = &(&binomial * &factor) * &polynomial! { 0 => 1.0 / (k + 1) as f32 };
+        }
+        Ok(interpolant)
+    }
+
+    /// Horner's method over the stored terms in descending power, with the `f32` coefficients converted to `T` and accumulated in `T`.
+    /// - Gaps between sparse powers are bridged by multiplying with `x^gap`.
+    fn horner<T: Float>(&self, x: T) -> T {
+        let mut terms = self.coeff_of_power.iter().rev();
+        let (mut prev_power, mut value) = match terms.next() {
+            Some((&power, &coeff)) => (power, T::from(coeff)),
+            None => return T::from(0.0),
+        };
+        for (&power, &coeff) in terms {
+            value = value * x.powi((prev_power - power) as i32) + T::from(coeff);
+            prev_power = power;
+        }
+        value * x.powi(prev_power as i32)
+    }
+
+    /// Value at `x` by Horner's method accumulated in `f32`, same as `at`.
+    pub fn at_f32(&self, x: f32) -> f32 {
+        self.at(x)
+    }
+
+    /// Value at `x` by Horner's method accumulated in `f64`, more accurate than the `f32` storage suggests.
+    pub fn at_f64(&self, x: f64) -> f64 {
+        self.horner(x)
+    }
+
+    /// Roots `(re, im)` inside the rectangle `re.0 < Re z < re.1`, `im.0 < Im z < im.1`, each to within `tol`.
+    /// - Counts roots with the argument principle and recursively subdivides the regions containing any,
+    ///   until they are smaller than `tol`, whose centers are returned (repeated by multiplicity).
+    /// - Subdivision is slightly off-center so that symmetric roots do not land on cut lines.
+    /// - Roots on the rectangle's boundary make the count unreliable.
+    pub fn roots_in_rectangle(&self, re: (f64, f64), im: (f64, f64), tol: f64) -> Vec<(f64, f64)> {
+        let p = self.to_dense_f64();
+        if p.len() < 2 {
+            return vec![];
+        }
+        let mut roots = vec![];
+        let mut regions = vec![(re, im, dense::winding_number(&p, re, im))];
+        while let Some((re, im, count)) = regions.pop() {
+            if count <= 0 {
+                continue;
+            }
+            let (width, height) = (re.1 - re.0, im.1 - im.0);
+            if width.max(height) <= tol {
+                let center = ((re.0 + re.1) / 2.0, (im.0 + im.1) / 2.0);
+                roots.extend(vec![center; count as usize]);
+                continue;
+            }
+            let halves = if width >= height {
+                let cut = re.0 + 0.4871 * width;
+                [((re.0, cut), im), ((cut, re.1), im)]
+            } else {
+                let cut = im.0 + 0.4871 * height;
+                [(re, (im.0, cut)), (re, (cut, im.1))]
+            };
+            let first = dense::winding_number(&p, halves[0].0, halves[0].1);
+            regions.push((halves[0].0, halves[0].1, first));
+            regions.push((halves[1].0, halves[1].1, count - first));
+        }
+        roots.sort_by(|s, t| s.0.total_cmp(&t.0).then(s.1.total_cmp(&t.1)));
+        roots
+    }
+
+    /// Partial derivatives of `root` with respect to each coefficient, `d root / d a_k = -root^k / p'(root)` at index `k`.
+    /// - Assumes `root` is a simple root, at multiple roots `p'(root)` vanishes and the entries are not finite.
+    /// - Empty for zero polynomial.
+    pub fn root_jacobian(&self, root: f64) -> Vec<f64> {
+        let p = self.to_dense_f64();
+        let slope = dense::derivative(&p)
+            .iter()
+            .rev()
+            .fold(0.0, |acc, c| acc * root + c);
+        (0..p.len()).map(|k| -root.powi(k as i32) / slope).collect()
+    }
+
+    /// `p(x^k)`, i.e. each power multiplied by `k`.
+    /// - For `k == 0` every term collapses to the constant `p(1)`.
+    pub fn substitute_power(&self, k: usize) -> Polynomial {
+        if k == 0 {
+            return Polynomial::monomial(0, self.at(1.0));
+        }
+        let mut substituted = Polynomial::new();
+        for (&power, &coeff) in self.coeff_of_power.iter() {
+            substituted.insert(power * k, coeff);
+        }
+        substituted
+    }
+
+    /// Copy without zero coefficient terms, along with the number of terms removed.
+    /// - The invariant normally holds, so this is for validating coefficient maps that were built directly.
+    pub fn with_nonzero_invariant_enforced(&self) -> (Polynomial, usize) {
+        let mut cleaned = Polynomial::new();
+        let mut removed = 0;
+        for (&power, &coeff) in self.coeff_of_power.iter() {
+            if coeff == 0.0 {
+                removed += 1;
+            } else {
+                cleaned.insert(power, coeff);
+            }
+        }
+        (cleaned, removed)
+    }
+
+    /// Vertices `(power, -ln|coeff|)` of the newton polygon, the lower convex hull of those points, sorted by power.
+    /// - Coefficient logarithms are negated so that the slope of each edge is the natural log of the magnitude
+    ///   of as many roots as the edge is wide, roughly, for coefficients of well separated magnitudes.
+    /// - Collinear points are not vertices, empty for zero polynomial.
+    pub fn newton_polygon(&self) -> Vec<(usize, f32)> {
+        let mut points = self
+            .coeff_of_power
+            .iter()
+            .map(|(&power, &coeff)| (power, -coeff.abs().ln()))
+            .collect::<Vec<(usize, f32)>>();
+        points.sort_by_key(|&(power, _)| power);
+        let mut hull: Vec<(usize, f32)> = vec![];
+        for point in points {
+            while hull.len() >= 2 {
+                let (a, b) = (hull[hull.len() - 2], hull[hull.len() - 1]);
+                // Pop b unless a -> b -> point turns counter clockwise
+                let cross =
+                    (b.0 - a.0) as f32 * (point.1 - a.1) - (b.1 - a.1) * (point.0 - a.0) as f32;
+                if cross > 0.0 {
+                    break;
+                }
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        hull
+    }
+
+    /// Value at `x` summing the terms `coeff * x^power` in increasing order of magnitude with compensated summation.
+    /// - Kahan–Babuška (Neumaier) variant, which also recovers low order bits lost when adding a larger term.
+    pub fn at_kahan(&self, x: f32) -> f32 {
+        let mut terms = self
+            .coeff_of_power
+            .iter()
+            .map(|(&power, &coeff)| coeff * x.powi(power as i32))
+            .collect::<Vec<f32>>();
+        terms.sort_by(|s, t| s.abs().total_cmp(&t.abs()));
+        let (mut sum, mut compensation) = (0f32, 0f32);
+        for term in terms {
+            let total = sum + term;
+            compensation += if sum.abs() >= term.abs() {
+                (sum - total) + term
+            } else {
+                (term - total) + sum
+            };
+            sum = total;
+        }
+        sum + compensation
+    }
+
+    /// All complex roots `(re, im)` as generalized eigenvalues of the companion pencil `a - lambda b`, by QZ iteration.
+    /// - `b = diag(a_n, 1, ..., 1)` carries the leading coefficient instead of dividing by it,
+    ///   which is more accurate than the monic companion matrix when `a_n` is small.
+    /// - Eigenvalues `alpha / beta` with `|beta| <= tol * |alpha|` lie at infinity and are dropped.
+    /// - Sorted by real then imaginary part, empty for constant polynomials or if the iteration does not converge.
+    pub fn roots_generalized(&self, tol: f64) -> Vec<(f64, f64)> {
+        let p = self.to_dense_f64();
+        if p.len() < 2 {
+            return vec![];
+        }
+        let (a, b) = dense::companion_pencil(&p);
+        let mut roots = linalg::hessenberg_triangular_eigenvalues(&a, &b)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(alpha, beta)| beta.norm() > tol * alpha.norm())
+            .map(|(alpha, beta)| {
+                let root = alpha / beta;
+                (root.re, root.im)
+            })
+            .collect::<Vec<(f64, f64)>>();
+        roots.sort_by(|s, t| s.0.total_cmp(&t.0).then(s.1.total_cmp(&t.1)));
+        roots
+    }
+
+    /// Legendre polynomial `P_n` by the recurrence `(k + 1) P_(k+1) = (2k + 1) x P_k - k P_(k-1)`.
+    pub fn legendre(n: usize) -> Polynomial {
+        let mut prev = polynomial! { 0 => 1.0 };
+        if n == 0 {
+            return prev;
+        }
+        let mut curr = polynomial! { 1 => 1.0 };
+        for k in 1..n {
+            let next = &(&(&polynomial! { 1 => (2 * k + 1) as f32 } * &curr)
+                - &(&polynomial! { 0 => k as f32 } * &prev))
+                * &polynomial! { 0 => 1.0 / (k + 1) as f32 };
+            prev = curr;
+            curr = next;
+        }
+        curr
+    }
+
+    /// Nodes and weights `(x, w)` of the `n`-point Gauss–Legendre rule on `[-1, 1]`.
+    /// - Nodes are the roots of `P_n`, found by Newton's method from Chebyshev-like guesses with `P_n` evaluated by its recurrence in `f64`.
+    fn gauss_legendre_nodes(n: usize) -> Vec<(f64, f64)> {
+        // (P_n(x), P_n'(x))
+        let legendre_at = |x: f64| {
+            let (mut prev, mut curr) = (1f64, x);
+            for k in 1..n {
+                let next = ((2 * k + 1) as f64 * x * curr - k as f64 * prev) / (k + 1) as f64;
+                prev = curr;
+                curr = next;
+            }
+            (curr, n as f64 * (x * curr - prev) / (x * x - 1.0))
+        };
+        (0..n)
+            .map(|i| {
+                let mut x = (std::f64::consts::PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+                for _ in 0..100 {
+                    let (value, slope) = legendre_at(x);
+                    let step = value / slope;
+                    x -= step;
+                    if step.abs() <= 1e-15 {
+                        break;
+                    }
+                }
+                let (_, slope) = legendre_at(x);
+                (x, 2.0 / ((1.0 - x * x) * slope * slope))
+            })
+            .collect()
+    }
+
+    /// Integral over `[a, b]` by `n`-point Gauss–Legendre quadrature, exact (up to rounding) for degree at most `2n - 1`.
+    /// - Panics if `n` is 0.
+    pub fn gauss_legendre_integrate(&self, a: f32, b: f32, n: usize) -> f32 {
+        assert!(n > 0, "Requested quadrature with 0 nodes.");
+        let (half_width, mid) = ((b as f64 - a as f64) / 2.0, (a as f64 + b as f64) / 2.0);
+        let sum = Polynomial::gauss_legendre_nodes(n)
+            .into_iter()
+            .map(|(x, w)| w * self.at((mid + half_width * x) as f32) as f64)
+            .sum::<f64>();
+        (half_width * sum) as f32
+    }
+
+    /// `(p(x), p(-x))` in one pass, as `even(x^2) + x odd(x^2)` and `even(x^2) - x odd(x^2)`.
+    /// - Costs about as much as a single `at`, without allocating.
+    pub fn at_pair(&self, x: f32) -> (f32, f32) {
+        let (even, odd) = self.parity_parts_at(x * x);
+        let odd = x * odd;
+        (even + odd, even - odd)
+    }
+
+    /// `self^exponent` by exponentiation by squaring, `pow(0)` is the constant one even for zero polynomial.
+    pub fn pow(&self, exponent: usize) -> Polynomial {
+        let mut base = self.clone();
+        let mut exponent = exponent;
+        let mut result = polynomial! { 0 => 1.0 };
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = &result * &base;
+            }
+            exponent /= 2;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /// Best approximation of degree at most `target_degree` in the `L2` sense over `[a, b]`.
+    /// - Projection onto `orthogonal_basis(target_degree, 1, a, b, quad_points)`, unlike dropping the higher terms.
+    pub fn reduce_degree(
+        &self,
+        target_degree: usize,
+        a: f32,
+        b: f32,
+        quad_points: usize,
+    ) -> Polynomial {
+        let basis = Polynomial::orthogonal_basis(target_degree, |_| 1.0, a, b, quad_points);
+        let coeffs = self.project(&basis, |_| 1.0, a, b, quad_points);
+        let mut reduced = Polynomial::new();
+        for (element, coeff) in basis.iter().zip(coeffs) {
+            reduced += &(element * coeff);
+        }
+        reduced
+    }
+
+    /// Number of distinct real roots in `(a, b]` by Sturm's theorem, `0` unless `a < b`.
+    /// - Chain `p, p', -(p mod p'), ...` with remainders trimmed by `rem_clean` as in `gcd`, so it also works for multiple roots.
+    /// - For zero polynomial `0` is returned.
+    pub fn sturm_root_count(&self, a: f32, b: f32) -> usize {
+        if self.is_zero() || a >= b {
+            return 0;
+        }
+        let tol = 1e-5
+            * self
+                .terms()
+                .fold(0f32, |max, (_, coeff)| max.max(coeff.abs()));
+        let mut chain = vec![self.clone(), self.derivative()];
+        while !chain[chain.len() - 1].is_zero() {
+            let n = chain.len();
+            let remainder = -chain[n - 2].rem_clean(&chain[n - 1], tol);
+            chain.push(remainder);
+        }
+        let sign_variations = |x: f32| {
+            let signs = chain
+                .iter()
+                .map(|p| p.at(x))
+                .filter(|&value| value != 0.0)
+                .map(|value| value > 0.0)
+                .collect::<Vec<bool>>();
+            signs.windows(2).filter(|pair| pair[0] != pair[1]).count()
+        };
+        sign_variations(a).saturating_sub(sign_variations(b))
+    }
+
+    /// Cauchy's bound `1 + max |a_i / a_n|` over `i < n`, all (complex) roots have absolute value below it.
+    /// - `None` for zero and constant polynomials.
+    pub fn root_bound(&self) -> Option<f32> {
+        match self.leading_term() {
+            Some((degree, lead)) if degree > 0 => Some(
+                1.0 + self
+                    .terms()
+                    .filter(|&(power, _)| power < degree)
+                    .fold(0f32, |max, (_, coeff)| max.max((coeff / lead).abs())),
+            ),
+            _ => None,
+        }
+    }
+
+    /// `(lower, upper)` bounds on the absolute values of the roots, `None` for constant polynomials.
+    /// - Upper bound is the smaller of Fujiwara's and (when all coefficients are non-zero) Kojima's bounds.
+    /// - Lower bound is the reciprocal of that bound for the reversed polynomial `x^n p(1 / x)`, `0` if `0` is a root.
+    pub fn root_bounds_kojima(&self) -> Option<(f32, f32)> {
+        let p = self.to_dense_f64();
+        if p.len() < 2 {
+            return None;
+        }
+        let upper = dense::root_modulus_upper_bound(&p);
+        let lower = if p[0] == 0.0 {
+            0.0
+        } else {
+            1.0 / dense::root_modulus_upper_bound(&dense::reverse(&p))
+        };
+        Some((lower as f32, upper as f32))
+    }
+
+    /// State space realization of the transfer function `num(s) / den(s)` in controllable canonical form.
+    /// - With `den` scaled to `s^n + a[n-1] s^(n-1) + ... + a[0]`, `A` is its companion matrix with ones on the
+    ///   superdiagonal and `-a[0], ..., -a[n-1]` on the last row, and `B = [0, ..., 0, 1]`.
+    /// - `D` is the direct feedthrough, `C` holds the coefficients of `num - D den` in increasing power.
+    pub fn to_controllable_canonical(
+        num: &Polynomial,
+        den: &Polynomial,
+    ) -> Result<StateSpace, SsError> {
+        let (n, lead) = den.leading_term().ok_or(SsError::ZeroDenominator)?;
+        if num.degree() > Some(n) {
+            return Err(SsError::Improper);
+        }
+        let coeff =
+            |p: &Polynomial, power| p.coeff_of_power.get(&power).cloned().unwrap_or(0.0) / lead;
+        let d = coeff(num, n);
+        let mut a = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            if i + 1 < n {
+                a[i][i + 1] = 1.0;
+            } else {
+                a[i] = (0..n).map(|k| -coeff(den, k)).collect();
+            }
+        }
+        let mut b = vec![0.0; n];
+        if let Some(last) = b.last_mut() {
+            *last = 1.0;
+        }
+        let c = (0..n).map(|k| coeff(num, k) - d * coeff(den, k)).collect();
+        Ok(StateSpace { a, b, c, d })
+    }
+
+    /// Wraps into a `TolerantPolynomial` comparing and hashing coefficients on a grid of spacing `epsilon`.
+    pub fn with_tolerance(self, epsilon: f32) -> TolerantPolynomial {
+        TolerantPolynomial {
+            polynomial: self,
+            epsilon,
+        }
+    }
+
+    /// `(1 - t) self + t other`, linear interpolation in coefficient space.
+    pub fn lerp(&self, other: &Polynomial, t: f32) -> Polynomial {
+        let mut interpolated = self * (1.0 - t);
+        interpolated.add_scaled(other, t);
+        interpolated
+    }
+
+    /// Quotient and remainder of long division by `divisor`, `self = quotient * divisor + remainder`.
+    /// - Each step cancels the actual leading term of the working remainder, so a rounding leftover
+    ///   at the cancelled power is divided out in a later step instead of being dropped.
+    /// - Stops once the working remainder has degree below the divisor's, which is then the remainder.
+    /// - Panics if `divisor` is zero polynomial.
+    pub fn div_rem(&self, divisor: &Polynomial) -> (Polynomial, Polynomial) {
+        let (divisor_degree, divisor_lead) = divisor
+            .leading_term()
+            .expect("Requested division with zero polynomial.");
+        let mut quotient = Polynomial::new();
+        let mut remainder = self.clone();
+        while let Some((degree, coeff)) = remainder.leading_term() {
+            if degree < divisor_degree {
+                break;
+            }
+            let term = Polynomial::monomial(degree - divisor_degree, coeff / divisor_lead);
+            remainder -= &(&term * divisor);
+            quotient += &term;
+            // A leftover that did not shrink (e.g. subnormal) would never cancel
+            if remainder[degree].abs() >= coeff.abs() {
+                remainder.coeff_of_power.remove(&degree);
+            }
+        }
+        (quotient, remainder)
+    }
+
+    /// Whether coefficients of every power differ by at most `tol`, absent terms counting as `0.0`.
+    pub fn approx_eq(&self, other: &Polynomial, tol: f32) -> bool {
+        self.terms()
+            .chain(other.terms())
+            .all(|(power, _)| (self[power] - other[power]).abs() <= tol)
+    }
+
+    /// Total order by degree (zero polynomial lowest), then by coefficients from the highest power down.
+    /// - Coefficients are compared with `f32::total_cmp`, so it is usable with `sort_by` even with NaNs.
+    pub fn cmp_by_degree(&self, other: &Polynomial) -> Ordering {
+        let degree = self.degree();
+        degree.cmp(&other.degree()).then_with(|| {
+            (0..=degree.unwrap_or(0))
+                .rev()
+                .map(|power| self[power].total_cmp(&other[power]))
+                .find(|&ordering| ordering != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+}
+
+/// Number of polynomials of each degree, zero polynomials are counted under `None`.
+pub fn degree_histogram(polys: &[&Polynomial]) -> BTreeMap<Option<usize>, usize> {
+    let mut histogram = BTreeMap::new();
+    for poly in polys.iter() {
+        *histogram.entry(poly.degree()).or_insert(0) += 1;
+    }
+    histogram
+}
+
+/// Function equal to `pieces[i]` on `[breakpoints[i], breakpoints[i + 1])`.
+/// - Invariant: `breakpoints` is strictly increasing and has one more element than `pieces`.
+/// - The last piece also covers the right most breakpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiecewisePolynomial {
+    pub breakpoints: Vec<f32>,
+    pub pieces: Vec<Polynomial>,
+}
+
+impl PiecewisePolynomial {
+    /// Value at `x`, `None` if `x` lies outside `[breakpoints[0], breakpoints[last]]`.
+    pub fn at(&self, x: f32) -> Option<f32> {
+        let (first, last) = match (self.breakpoints.first(), self.breakpoints.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return None,
+        };
+        if self.pieces.is_empty() || !(first <= x && x <= last) {
+            return None;
+        }
+        let piece = self
+            .breakpoints
+            .partition_point(|&breakpoint| breakpoint <= x)
+            .saturating_sub(1)
+            .min(self.pieces.len() - 1);
+        Some(self.pieces[piece].at(x))
+    }
+
+    /// Jump `pieces[i](b) - pieces[i - 1](b)` at each interior breakpoint `b = breakpoints[i]`.
+    pub fn continuity_defects(&self) -> Vec<f32> {
+        self.pieces
+            .windows(2)
+            .zip(self.breakpoints.iter().skip(1))
+            .map(|(pair, &breakpoint)| pair[1].at(breakpoint) - pair[0].at(breakpoint))
+            .collect()
+    }
+}
+
+/// Polynomial whose `PartialEq` and `Hash` round coefficients to multiples of `epsilon` first,
+/// so polynomials differing by rounding errors collide in collections.
+/// - Coefficients on opposite sides of a grid boundary still differ, however close they are.
+#[derive(Debug, Clone)]
+pub struct TolerantPolynomial {
+    pub polynomial: Polynomial,
+    pub epsilon: f32,
+}
+
+impl TolerantPolynomial {
+    /// Non-zero `(power, coeff / epsilon rounded)` in increasing power.
+    fn quantized(&self) -> BTreeMap<usize, i64> {
+        self.polynomial
+            .terms()
+            .map(|(power, coeff)| (power, (coeff / self.epsilon).round() as i64))
+            .filter(|&(_, steps)| steps != 0)
+            .collect()
+    }
+}
+
+impl PartialEq for TolerantPolynomial {
+    fn eq(&self, other: &Self) -> bool {
+        self.epsilon == other.epsilon && self.quantized() == other.quantized()
+    }
+}
+
+impl Eq for TolerantPolynomial {}
+
+impl Hash for TolerantPolynomial {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.quantized().hash(state);
+    }
+}
+
+/// State space model `x' = A x + B u`, `y = C x + D u` of a single input single output system.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateSpace {
+    /// State matrix as rows.
+    pub a: Vec<Vec<f32>>,
+    /// Input column.
+    pub b: Vec<f32>,
+    /// Output row.
+    pub c: Vec<f32>,
+    /// Direct feedthrough.
+    pub d: f32,
+}
+
+/// Reasons sampling a polynomial over a grid can fail.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SampleError {
+    /// Less than 2 samples were requested.
+    TooFewSamples,
+    /// Left bound is not strictly less than right bound.
+    EmptyRange,
+    /// Logarithmic grids need a strictly positive left bound.
+    NonPositiveBound,
+}
+
+/// Reasons an interpolating polynomial can not be constructed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InterpError {
+    /// Not enough points to interpolate.
+    TooFewPoints,
+    /// x-coordinates are not sorted or contain duplicates.
+    NotStrictlyIncreasing,
+    /// Two points share the same x-coordinate.
+    DuplicateX,
+    /// x-coordinates are not equally spaced.
+    UnequalSpacing,
+}
+
+/// Reasons a Padé approximant can not be computed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PadeError {
+    /// The linear system for the denominator coefficients is singular.
+    Singular,
+}
+
+/// Output buffer length does not match the number of inputs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LenMismatch {
+    pub expected: usize,
+    pub actual: usize,
+}
+
+/// Degree of a polynomial exceeds the allowed maximum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DegreeError {
+    pub actual: usize,
+    pub max: usize,
+}
+
+/// Reasons a plot can not be drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlotError {
+    /// Less than 2 samples were requested.
+    TooFewSamples,
+    /// Left bound is not strictly less than right bound.
+    EmptyRange,
+    /// Clipping bound is not strictly positive.
+    NonPositiveClip,
+}
+
+/// Reasons a transfer function can not be realized in state space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SsError {
+    /// Numerator degree exceeds denominator degree.
+    Improper,
+    /// Denominator is zero polynomial.
+    ZeroDenominator,
+}
+
+/// Floating point types that `Polynomial::horner` can accumulate in.
+trait Float: From<f32> + Copy + Add<Output = Self> + Mul<Output = Self> {
+    fn powi(self, n: i32) -> Self;
+}
+
+impl Float for f32 {
+    fn powi(self, n: i32) -> f32 {
+        f32::powi(self, n)
+    }
+}
+
+impl Float for f64 {
+    fn powi(self, n: i32) -> f64 {
+        f64::powi(self, n)
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format_terms('x', None, false))
+    }
+}
+
+/// Zero polynomial.
+impl Default for Polynomial {
+    fn default() -> Self {
+        Polynomial::new()
+    }
+}
+
+/// Sum of all polynomials, zero polynomial for none.
+impl Sum for Polynomial {
+    fn sum<I: Iterator<Item = Polynomial>>(iter: I) -> Self {
+        iter.fold(Polynomial::default(), |mut sum, poly| {
+            sum += &poly;
+            sum
+        })
+    }
+}
+
+impl<'a> Sum<&'a Polynomial> for Polynomial {
+    fn sum<I: Iterator<Item = &'a Polynomial>>(iter: I) -> Self {
+        iter.fold(Polynomial::default(), |mut sum, poly| {
+            sum += poly;
+            sum
+        })
+    }
+}
+
+/// Polynomial with `coeffs[power]` as the coefficient of `x^power`, zero entries are skipped.
+impl From<Vec<f32>> for Polynomial {
+    fn from(coeffs: Vec<f32>) -> Self {
+        let mut poly = Polynomial::new();
+        for (power, coeff) in coeffs.into_iter().enumerate() {
+            poly.insert(power, coeff);
+        }
+        poly
+    }
+}
+
+/// Polynomial of the `(power, coeff)` terms, coefficients of repeated powers are added up.
+impl FromIterator<(usize, f32)> for Polynomial {
+    fn from_iter<I: IntoIterator<Item = (usize, f32)>>(iter: I) -> Self {
+        let mut poly = Polynomial::new();
+        poly.extend(iter);
+        poly
+    }
+}
+
+/// Adds each `(power, coeff)` term, terms that cancel out are dropped.
+impl Extend<(usize, f32)> for Polynomial {
+    fn extend<I: IntoIterator<Item = (usize, f32)>>(&mut self, iter: I) {
+        for (power, coeff) in iter {
+            self.add_term(power, coeff);
+        }
+    }
+}
+
+/// Parses sums of terms in `x` such as `3x^2 - 5x + 6`, `-2x^{3} + x` or the `Display` output.
+/// - Whitespace is ignored, powers may be written `x^2` or `x^{2}`, a bare `x` has power 1.
+/// - Missing coefficients are 1, terms of the same power are accumulated.
+impl FromStr for Polynomial {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+        if s.is_empty() {
+            return Err("Empty string is not a polynomial.".to_string());
+        }
+        // Split before each sign that is not part of a power or an exponent
+        let mut terms = vec![];
+        let mut start = 0;
+        let mut prev = None;
+        for (i, c) in s.char_indices() {
+            if (c == '+' || c == '-') && i > 0 && !matches!(prev, Some('^' | '{' | 'e' | 'E')) {
+                terms.push(&s[start..i]);
+                start = i;
+            }
+            prev = Some(c);
+        }
+        terms.push(&s[start..]);
+        let mut poly = Polynomial::new();
+        for term in terms {
+            let (sign, unsigned) = match term.strip_prefix('-') {
+                Some(unsigned) => (-1.0, unsigned),
+                None => (1.0, term.strip_prefix('+').unwrap_or(term)),
+            };
+            let invalid = || format!("Invalid term '{}'.", term);
+            let (coeff, power) = match unsigned.find('x') {
+                Some(i) => {
+                    let coeff = unsigned[..i].strip_suffix('*').unwrap_or(&unsigned[..i]);
+                    let power = match unsigned[i + 1..].strip_prefix('^') {
+                        Some(power) => power
+                            .strip_prefix('{')
+                            .and_then(|power| power.strip_suffix('}'))
+                            .unwrap_or(power)
+                            .parse::<usize>()
+                            .map_err(|_| invalid())?,
+                        None if unsigned[i + 1..].is_empty() => 1,
+                        None => return Err(invalid()),
+                    };
+                    (coeff, power)
+                }
+                None => (unsigned, 0),
+            };
+            let coeff = if coeff.is_empty() && power > 0 {
+                1.0
+            } else {
+                coeff.parse::<f32>().map_err(|_| invalid())?
+            };
+            poly.add_term(power, sign * coeff);
+        }
+        Ok(poly)
+    }
+}
+
+/// Coefficient of `x^power`, absent powers refer to a shared zero.
+/// - There is no `IndexMut`, as assigning zero through it would break the non-zero invariant, use `insert` instead.
+impl Index<usize> for Polynomial {
+    type Output = f32;
+
+    fn index(&self, power: usize) -> &f32 {
+        static ZERO: f32 = 0.0;
+        self.coeff_of_power.get(&power).unwrap_or(&ZERO)
+    }
+}
+
+impl<'a, 'b> Add<&'b Polynomial> for &'a Polynomial {
+    type Output = Polynomial;
+
+    fn add(self, other: &'b Polynomial) -> Polynomial {
+        let mut sum = self.clone();
+        for (&power, &coeff) in other.coeff_of_power.iter() {
+            sum.insert(
+                power,
+                match sum.coeff_of_power.get(&power) {
+                    Some(&prev_coeff) => prev_coeff + coeff,
+                    None => coeff,
+                },
+            );
+        }
+        sum
+    }
+}
+
+impl<'b> AddAssign<&'b Polynomial> for Polynomial {
+    fn add_assign(&mut self, other: &'b Polynomial) {
+        for (&power, &coeff) in other.coeff_of_power.iter() {
+            self.add_term(power, coeff);
+        }
+    }
+}
+
+impl<'a, 'b> Sub<&'b Polynomial> for &'a Polynomial {
+    type Output = Polynomial;
+
+    fn sub(self, other: &'b Polynomial) -> Polynomial {
+        let mut difference = self.clone();
+        for (&power, &coeff) in other.coeff_of_power.iter() {
+            difference.insert(
+                power,
+                match difference.coeff_of_power.get(&power) {
+                    Some(&prev_coeff) => prev_coeff - coeff,
+                    None => -coeff,
+                },
+            );
+        }
+        difference
+    }
+}
+
+impl<'b> SubAssign<&'b Polynomial> for Polynomial {
+    fn sub_assign(&mut self, other: &'b Polynomial) {
+        for (&power, &coeff) in other.coeff_of_power.iter() {
+            self.insert(
+                power,
+                match self.coeff_of_power.get(&power) {
+                    Some(&prev_coeff) => prev_coeff - coeff,
+                    None => -coeff,
+                },
+            );
+        }
+    }
+}
+
+impl Neg for &Polynomial {
+    type Output = Polynomial;
+
+    fn neg(self) -> Polynomial {
+        let mut negated = Polynomial::new();
+        for (&power, &coeff) in self.coeff_of_power.iter() {
+            negated.insert(power, -coeff);
+        }
+        negated
+    }
+}
+
+impl Neg for Polynomial {
+    type Output = Polynomial;
+
+    fn neg(self) -> Polynomial {
+        -&self
+    }
+}
+
+impl<'a, 'b> Mul<&'b Polynomial> for &'a Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, other: &'b Polynomial) -> Polynomial {
+        let mut product = Polynomial::new();
+        for (&a_power, &a_coeff) in self.coeff_of_power.iter() {
+            let mut term_mul = Polynomial::new();
+            // Since any term * b will result in non-overlapping terms, simple insert can be used instead of repeated polynomial addition
+            for (&b_power, &b_coeff) in other.coeff_of_power.iter() {
+                term_mul.insert(a_power + b_power, a_coeff * b_coeff);
+            }
+            // Here there can be overlaps and hence polynomial addition is required
+            product += &term_mul;
+        }
+        product
+    }
+}
+
+impl Mul<f32> for &Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, scalar: f32) -> Polynomial {
+        let mut scaled = Polynomial::new();
+        for (&power, &coeff) in self.coeff_of_power.iter() {
+            scaled.insert(power, coeff * scalar);
+        }
+        scaled
+    }
+}
+
+impl Mul<f32> for Polynomial {
+    type Output = Polynomial;
+
+    fn mul(self, scalar: f32) -> Polynomial {
+        &self * scalar
+    }
+}
+
+impl Mul<&Polynomial> for f32 {
+    type Output = Polynomial;
+
+    fn mul(self, poly: &Polynomial) -> Polynomial {
+        poly * self
+    }
+}
+
+impl Mul<Polynomial> for f32 {
+    type Output = Polynomial;
+
+    fn mul(self, poly: Polynomial) -> Polynomial {
+        &poly * self
+    }
+}
+
+impl<'a, 'b> Div<&'b Polynomial> for &'a Polynomial {
+    type Output = Polynomial;
+
+    fn div(self, divisor: &'b Polynomial) -> Polynomial {
+        self.div_rem(divisor).0
     }
 }
 
@@ -329,6 +2797,6 @@ impl<'a, 'b> Rem<&'b Polynomial> for &'a Polynomial {
     type Output = Polynomial;
 
     fn rem(self, other: &'b Polynomial) -> Polynomial {
-        self - &(&(self / other) * &other)
+        self.div_rem(other).1
     }
 }
@@ -0,0 +1,328 @@
+use crate::complex::Complex;
+use std::ops::Range;
+
+/// Least squares solution of the over-determined system `a * x = b` using Householder QR.
+/// - `a` is given as rows, all of the same length.
+/// - Returns `None` if `a` has fewer rows than columns or is rank deficient.
+pub(crate) fn least_squares(a: &[Vec<f64>], b: &[f64]) -> Option<Vec<f64>> {
+    let rows = a.len();
+    let cols = a.first().map_or(0, |row| row.len());
+    if rows < cols || rows != b.len() {
+        return None;
+    }
+    // Column major copy of a, so that reflections are applied column by column
+    let mut r = (0..cols)
+        .map(|j| a.iter().map(|row| row[j]).collect())
+        .collect::<Vec<Vec<f64>>>();
+    let mut qtb = b.to_vec();
+    for k in 0..cols {
+        let norm = r[k][k..].iter().map(|rik| rik * rik).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            return None;
+        }
+        let alpha = if r[k][k] > 0.0 { -norm } else { norm };
+        let mut v = r[k][k..].to_vec();
+        v[0] -= alpha;
+        let v_norm_sq = v.iter().map(|vi| vi * vi).sum::<f64>();
+        if v_norm_sq == 0.0 {
+            continue;
+        }
+        for column in r.iter_mut().skip(k).chain(std::iter::once(&mut qtb)) {
+            let dot = v
+                .iter()
+                .zip(&column[k..])
+                .map(|(vi, ci)| vi * ci)
+                .sum::<f64>();
+            let factor = 2.0 * dot / v_norm_sq;
+            for (ci, vi) in column[k..].iter_mut().zip(&v) {
+                *ci -= factor * vi;
+            }
+        }
+    }
+    let scale = (0..cols).map(|k| r[k][k].abs()).fold(0.0, f64::max);
+    let mut x = vec![0.0; cols];
+    for k in (0..cols).rev() {
+        if r[k][k].abs() <= scale * 1e-12 {
+            return None;
+        }
+        let tail = (k + 1..cols).map(|j| r[j][k] * x[j]).sum::<f64>();
+        x[k] = (qtb[k] - tail) / r[k][k];
+    }
+    Some(x)
+}
+
+/// Solution of the square system `a * x = b` using gaussian elimination with partial pivoting.
+/// - `a` is given as rows.
+/// - Returns `None` if `a` is singular (up to rounding) or the dimensions do not match.
+pub(crate) fn solve(a: &[Vec<f64>], b: &[f64]) -> Option<Vec<f64>> {
+    let n = a.len();
+    if b.len() != n || a.iter().any(|row| row.len() != n) {
+        return None;
+    }
+    let mut augmented = a
+        .iter()
+        .zip(b)
+        .map(|(row, &bi)| {
+            let mut row = row.clone();
+            row.push(bi);
+            row
+        })
+        .collect::<Vec<Vec<f64>>>();
+    let scale = a
+        .iter()
+        .flat_map(|row| row.iter())
+        .fold(0f64, |max, aij| max.max(aij.abs()));
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|&i, &j| augmented[i][k].abs().total_cmp(&augmented[j][k].abs()))
+            .unwrap();
+        if augmented[pivot][k].abs() <= scale * 1e-12 {
+            return None;
+        }
+        augmented.swap(k, pivot);
+        let (upper, lower) = augmented.split_at_mut(k + 1);
+        let pivot_row = &upper[k];
+        for row in lower.iter_mut() {
+            let factor = row[k] / pivot_row[k];
+            for (rj, pj) in row[k..].iter_mut().zip(&pivot_row[k..]) {
+                *rj -= factor * pj;
+            }
+        }
+    }
+    let mut x = vec![0.0; n];
+    for k in (0..n).rev() {
+        let tail = (k + 1..n).map(|j| augmented[k][j] * x[j]).sum::<f64>();
+        x[k] = (augmented[k][n] - tail) / augmented[k][k];
+    }
+    Some(x)
+}
+
+/// Balances the square matrix `a` (given as rows) in place by a diagonal similarity `D^-1 a D`.
+/// - Parlett–Reinsch iteration with powers of 2, so no rounding is introduced.
+/// - Rows and columns end up with comparable norms, which makes eigenvalues better conditioned.
+pub(crate) fn balance(a: &mut [Vec<f64>]) {
+    let n = a.len();
+    let mut converged = false;
+    while !converged {
+        converged = true;
+        for i in 0..n {
+            let column_norm = (0..n)
+                .filter(|&j| j != i)
+                .map(|j| a[j][i].abs())
+                .sum::<f64>();
+            let row_norm = (0..n)
+                .filter(|&j| j != i)
+                .map(|j| a[i][j].abs())
+                .sum::<f64>();
+            if column_norm == 0.0 || row_norm == 0.0 {
+                continue;
+            }
+            let (mut c, mut f) = (column_norm, 1.0);
+            while c < row_norm / 2.0 {
+                c *= 4.0;
+                f *= 2.0;
+            }
+            while c >= row_norm * 2.0 {
+                c /= 4.0;
+                f /= 2.0;
+            }
+            if (c + row_norm / f) < 0.95 * (column_norm + row_norm) {
+                converged = false;
+                for aij in a[i].iter_mut() {
+                    *aij /= f;
+                }
+                for row in a.iter_mut() {
+                    row[i] *= f;
+                }
+            }
+        }
+    }
+}
+
+/// Givens rotation `(c, s)` with `[c, s; -conj(s), c] [x; y] = [r; 0]`.
+fn givens(x: Complex, y: Complex) -> (f64, Complex) {
+    let r = x.norm().hypot(y.norm());
+    if x.norm() == 0.0 {
+        (0.0, Complex::new(1.0, 0.0))
+    } else {
+        let phase = x.scale(1.0 / x.norm());
+        (x.norm() / r, (phase * y.conj()).scale(1.0 / r))
+    }
+}
+
+/// Multiplies rows `k` and `k + 1` of `m`, restricted to `columns`, by `[c, s; -conj(s), c]` from the left.
+fn rotate_rows(m: &mut [Vec<Complex>], k: usize, (c, s): (f64, Complex), columns: Range<usize>) {
+    let (above, below) = m.split_at_mut(k + 1);
+    for (upper, lower) in above[k][columns.clone()]
+        .iter_mut()
+        .zip(&mut below[0][columns])
+    {
+        let (u, l) = (*upper, *lower);
+        *upper = u.scale(c) + s * l;
+        *lower = l.scale(c) - s.conj() * u;
+    }
+}
+
+/// Multiplies columns `k` and `k + 1` of `m`, restricted to `rows`, by the adjoint of `[c, s; -conj(s), c]` from the right.
+fn rotate_columns(m: &mut [Vec<Complex>], k: usize, (c, s): (f64, Complex), rows: Range<usize>) {
+    for row in m[rows].iter_mut() {
+        let (left, right) = (row[k], row[k + 1]);
+        row[k] = left.scale(c) + right * s.conj();
+        row[k + 1] = right.scale(c) - left * s;
+    }
+}
+
+/// Eigenvalues of the upper hessenberg matrix `h` (given as rows) by shifted complex QR iteration.
+/// - Uses Wilkinson shifts with deflation of negligible subdiagonal entries.
+/// - Returns `None` if it does not converge within `30 * max(10, n)` sweeps in total.
+pub(crate) fn hessenberg_eigenvalues(h: &[Vec<f64>]) -> Option<Vec<Complex>> {
+    let mut h = h
+        .iter()
+        .map(|row| row.iter().map(|&hij| Complex::new(hij, 0.0)).collect())
+        .collect::<Vec<Vec<Complex>>>();
+    let mut eigenvalues = vec![];
+    let mut hi = h.len();
+    let mut iterations = 0;
+    let mut budget = 30 * h.len().max(10);
+    while hi > 0 {
+        // Active window is [lo, hi), everything below it has been deflated
+        let mut lo = hi - 1;
+        while lo > 0 {
+            let off_diagonal = h[lo][lo - 1].norm();
+            if off_diagonal <= f64::EPSILON * (h[lo - 1][lo - 1].norm() + h[lo][lo].norm()) {
+                h[lo][lo - 1] = Complex::new(0.0, 0.0);
+                break;
+            }
+            lo -= 1;
+        }
+        if lo == hi - 1 {
+            eigenvalues.push(h[lo][lo]);
+            hi -= 1;
+            iterations = 0;
+            continue;
+        }
+        iterations += 1;
+        if budget == 0 {
+            return None;
+        }
+        budget -= 1;
+        let shift = if iterations % 10 == 0 {
+            // Exceptional shift to break cycles
+            h[hi - 1][hi - 1] + Complex::new(0.75, 0.4375).scale(h[hi - 1][hi - 2].norm())
+        } else {
+            let (a, b) = (h[hi - 2][hi - 2], h[hi - 2][hi - 1]);
+            let (c, d) = (h[hi - 1][hi - 2], h[hi - 1][hi - 1]);
+            let half_diff = (a - d).scale(0.5);
+            let root = (half_diff * half_diff + b * c).sqrt();
+            let mean = (a + d).scale(0.5);
+            let (s1, s2) = (mean + root, mean - root);
+            if (s1 - d).norm() <= (s2 - d).norm() {
+                s1
+            } else {
+                s2
+            }
+        };
+        for (k, row) in h.iter_mut().enumerate().take(hi).skip(lo) {
+            row[k] = row[k] - shift;
+        }
+        // h - shift = q r by givens rotations, then h = r q + shift
+        let mut rotations = vec![];
+        for k in lo..hi - 1 {
+            let rotation = givens(h[k][k], h[k + 1][k]);
+            rotate_rows(&mut h, k, rotation, k..hi);
+            rotations.push(rotation);
+        }
+        for (k, &rotation) in (lo..hi - 1).zip(&rotations) {
+            rotate_columns(&mut h, k, rotation, lo..(k + 3).min(hi));
+        }
+        for (k, row) in h.iter_mut().enumerate().take(hi).skip(lo) {
+            row[k] = row[k] + shift;
+        }
+    }
+    Some(eigenvalues)
+}
+
+/// Generalized eigenvalues `(alpha, beta)` of the pencil `h - lambda t`, i.e. `lambda = alpha / beta`,
+/// by single shift complex QZ iteration.
+/// - `h` must be upper hessenberg and `t` upper triangular, both given as rows.
+/// - `beta` (close to) zero indicates an eigenvalue at infinity.
+/// - Returns `None` if it does not converge within `30 * max(10, n)` sweeps in total.
+pub(crate) fn hessenberg_triangular_eigenvalues(
+    h: &[Vec<f64>],
+    t: &[Vec<f64>],
+) -> Option<Vec<(Complex, Complex)>> {
+    let to_complex = |m: &[Vec<f64>]| {
+        m.iter()
+            .map(|row| row.iter().map(|&mij| Complex::new(mij, 0.0)).collect())
+            .collect::<Vec<Vec<Complex>>>()
+    };
+    let (mut h, mut t) = (to_complex(h), to_complex(t));
+    let mut eigenvalues = vec![];
+    let mut hi = h.len();
+    let mut iterations = 0;
+    let mut budget = 30 * h.len().max(10);
+    while hi > 0 {
+        // Active window is [lo, hi), everything below it has been deflated
+        let mut lo = hi - 1;
+        while lo > 0 {
+            let off_diagonal = h[lo][lo - 1].norm();
+            if off_diagonal <= f64::EPSILON * (h[lo - 1][lo - 1].norm() + h[lo][lo].norm()) {
+                h[lo][lo - 1] = Complex::new(0.0, 0.0);
+                break;
+            }
+            lo -= 1;
+        }
+        if lo == hi - 1 {
+            eigenvalues.push((h[lo][lo], t[lo][lo]));
+            hi -= 1;
+            iterations = 0;
+            continue;
+        }
+        iterations += 1;
+        if budget == 0 {
+            return None;
+        }
+        budget -= 1;
+        // Eigenvalue of the trailing 2x2 pencil closest to d / g, found as a root of
+        // det([a, b; c, d] - shift [e, f; 0, g]) = eg shift^2 - (ag + de - cf) shift + (ad - bc)
+        let (a, b) = (h[hi - 2][hi - 2], h[hi - 2][hi - 1]);
+        let (c, d) = (h[hi - 1][hi - 2], h[hi - 1][hi - 1]);
+        let (e, f, g) = (t[hi - 2][hi - 2], t[hi - 2][hi - 1], t[hi - 1][hi - 1]);
+        let (qa, qb, qc) = (e * g, -(a * g + d * e - c * f), a * d - b * c);
+        let discriminant = (qb * qb - (qa * qc).scale(4.0)).sqrt();
+        let q = if (qb + discriminant).norm() >= (qb - discriminant).norm() {
+            -(qb + discriminant).scale(0.5)
+        } else {
+            -(qb - discriminant).scale(0.5)
+        };
+        let mut shift = [(q, qa), (qc, q)]
+            .iter()
+            .filter(|(_, denominator)| denominator.norm() != 0.0)
+            .map(|&(numerator, denominator)| numerator / denominator)
+            .min_by(|s, t| (*s * g - d).norm().total_cmp(&(*t * g - d).norm()))
+            .unwrap_or(Complex::new(0.0, 0.0));
+        if iterations % 10 == 0 {
+            // Exceptional shift to break cycles
+            shift = shift + Complex::new(0.75, 0.4375).scale(c.norm());
+        }
+        // Implicit step: a rotation from the first column of (h - shift t), then chase the bulges down
+        let mut rotation = givens(h[lo][lo] - shift * t[lo][lo], h[lo + 1][lo]);
+        for k in lo..hi - 1 {
+            if k > lo {
+                rotation = givens(h[k][k - 1], h[k + 1][k - 1]);
+            }
+            rotate_rows(&mut h, k, rotation, k.max(lo + 1) - 1..hi);
+            rotate_rows(&mut t, k, rotation, k..hi);
+            if k > lo {
+                h[k + 1][k - 1] = Complex::new(0.0, 0.0);
+            }
+            // Right rotation zeroing the fill in t[k + 1][k]
+            let (c, s) = givens(t[k + 1][k + 1].conj(), t[k + 1][k].conj());
+            let rotation = (c, -s.conj());
+            rotate_columns(&mut h, k, rotation, lo..(k + 3).min(hi));
+            rotate_columns(&mut t, k, rotation, lo..k + 2);
+            t[k + 1][k] = Complex::new(0.0, 0.0);
+        }
+    }
+    Some(eigenvalues)
+}
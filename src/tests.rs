@@ -1,6 +1,18 @@
 #[cfg(test)]
 mod tests {
-    use crate::{polynomial, Polynomial};
+    use crate::{
+        polynomial, DegreeError, InterpError, LenMismatch, PadeError, PiecewisePolynomial,
+        PlotError, Polynomial, SampleError, SsError, StateSpace,
+    };
+    use std::cmp::Ordering;
+    use std::collections::HashSet;
+
+    #[test]
+    fn monomial() {
+        assert_eq!(Polynomial::monomial(3, 2.0), polynomial! { 3 => 2.0 });
+        assert_eq!(Polynomial::monomial(0, -1.5), polynomial! { 0 => -1.5 });
+        assert_eq!(Polynomial::monomial(5, 0.0), Polynomial::new());
+    }
 
     #[test]
     fn degree() {
@@ -21,25 +33,173 @@ mod tests {
         assert_eq!(Polynomial::new().degree(), None);
     }
 
+    #[test]
+    fn leading_term() {
+        assert_eq!(
+            polynomial! { 4 => 3.0, 1 => 1.0 }.leading_term(),
+            Some((4, 3.0))
+        );
+        assert_eq!(
+            polynomial! { 7 => 0.0, 0 => -2.0 }.leading_term(),
+            Some((0, -2.0))
+        );
+        assert_eq!(Polynomial::new().leading_term(), None);
+    }
+
     #[test]
     fn at() {
         let p = polynomial! { 1 => 1.0, 2 => 5.0, 0 => 5.0, 3 => -2.0, 4 => -1.0, 5 => 1.0 };
         assert_eq!(p.at(3.0), 161.0);
     }
 
+    #[test]
+    fn at_checked() {
+        let p = polynomial! { 20 => 1.0, 1 => 1.0 };
+        assert_eq!(p.at_checked(1e3), None);
+        assert_eq!(p.at_checked(-1e3), None);
+        assert_eq!(p.at_checked(1.0), Some(2.0));
+        let q = polynomial! { 2 => 1.0, 0 => -1.0 };
+        assert_eq!(q.at_checked(3.0), Some(8.0));
+        assert_eq!(q.at_checked(f32::NAN), None);
+    }
+
+    #[test]
+    fn at_complex() {
+        let p = polynomial! { 2 => 1.0, 0 => 1.0 };
+        assert_eq!(p.at_complex(0.0, 1.0), (0.0, 0.0));
+        assert_eq!(p.at_complex(1.0, 1.0), (1.0, 2.0));
+        assert_eq!(p.at_complex(3.0, 0.0), (10.0, 0.0));
+        assert_eq!(Polynomial::new().at_complex(1.0, 1.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn sample_complex_grid() {
+        let p = polynomial! { 3 => 1.0, 1 => -2.0, 0 => 0.5 };
+        let grid = p.sample_complex_grid((-1.0, 2.0), (0.0, 1.0), 4, 3);
+        assert_eq!(grid.len(), 3);
+        assert!(grid.iter().all(|row| row.len() == 4));
+        for (j, row) in grid.iter().enumerate() {
+            for (i, &value) in row.iter().enumerate() {
+                assert_eq!(value, p.at_complex(-1.0 + i as f64, 0.5 * j as f64));
+            }
+        }
+        assert_eq!(
+            p.sample_complex_grid((1.0, 2.0), (3.0, 4.0), 1, 1),
+            vec![vec![p.at_complex(1.0, 3.0)]]
+        );
+    }
+
     #[test]
     fn plot() {
         let p = polynomial! { 4 => 0.0, 3 => -1.0, 2 => -10.0, 1 => 10.0, 0 => 15.0 };
         let q = polynomial! { 5 => 0.0, 2 => -5.0, 1 => -1.0, 0 => 30.0 };
         let r = polynomial! { 6 => 0.0, 1 => -100.0, 0 => 30.0 };
+        let path = std::env::temp_dir().join("plot_test");
+        let path = path.to_str().unwrap();
         assert_eq!(
-            Polynomial::plot(&[&p, &q, &r], -13.0, 5.0, 50, "plot_test"),
+            Polynomial::plot(&[&p, &q, &r], -13.0, 5.0, 50, path),
             Ok(())
         );
+        std::fs::remove_file(format!("{}.gnuplot", path)).unwrap();
         assert_eq!(
             Polynomial::plot(&[&p, &q, &r], -13.0, 5.0, 1, "should_not_exist"),
             Err("Requested less than 2 samples for plotting.")
         );
+        for &(l, r) in [(5.0, 5.0), (5.0, -13.0), (f32::NAN, 5.0)].iter() {
+            assert_eq!(
+                Polynomial::plot(&[&p], l, r, 50, "should_not_exist"),
+                Err("Left bound must be strictly less than right bound.")
+            );
+        }
+    }
+
+    #[test]
+    fn plot_to_image() {
+        let p = polynomial! { 3 => -1.0, 2 => -10.0, 1 => 10.0, 0 => 15.0 };
+        let q = polynomial! { 2 => -5.0, 1 => -1.0, 0 => 30.0 };
+        assert!(Polynomial::plot_to_image(&[&p], -13.0, 5.0, 50, "should_not_exist.jpg").is_err());
+        assert!(Polynomial::plot_to_image(&[&p], -13.0, 5.0, 50, "should_not_exist").is_err());
+        assert!(Polynomial::plot_to_image(&[&p], -13.0, 5.0, 1, "should_not_exist.png").is_err());
+        assert!(Polynomial::plot_to_image(&[&p], 5.0, 5.0, 50, "should_not_exist.png").is_err());
+        let gnuplot_installed = std::process::Command::new("gnuplot")
+            .arg("--version")
+            .output()
+            .is_ok();
+        if !gnuplot_installed {
+            // Rendering needs gnuplot, without it the failure must be reported
+            let path = std::env::temp_dir().join("plot_to_image_test_no_gnuplot.png");
+            let path = path.to_str().unwrap();
+            assert!(Polynomial::plot_to_image(&[&p], -13.0, 5.0, 50, path).is_err());
+            assert!(std::fs::metadata(path).is_err());
+            return;
+        }
+        for extension in ["png", "svg"].iter() {
+            let path = std::env::temp_dir().join(format!("plot_to_image_test.{}", extension));
+            let path = path.to_str().unwrap();
+            assert_eq!(
+                Polynomial::plot_to_image(&[&p, &q], -13.0, 5.0, 50, path),
+                Ok(())
+            );
+            assert!(std::fs::metadata(path).unwrap().len() > 0);
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn build_figure() {
+        let p = polynomial! { 3 => -1.0, 2 => -10.0, 1 => 10.0, 0 => 15.0 };
+        let mut fg = Polynomial::build_figure(&[&p], -13.0, 5.0, 50).unwrap();
+        fg.axes2d().lines(
+            vec![-13.0, 5.0],
+            vec![0.0, 0.0],
+            &[gnuplot::Caption("axis"), gnuplot::Color("black")],
+        );
+        let path = std::env::temp_dir().join("build_figure_test.gnuplot");
+        fg.echo_to_file(&path);
+        assert!(std::fs::metadata(&path).unwrap().len() > 0);
+        std::fs::remove_file(&path).unwrap();
+        assert!(Polynomial::build_figure(&[&p], -13.0, 5.0, 1).is_err());
+    }
+
+    #[test]
+    fn plot_styled() {
+        let p = polynomial! { 3 => -1.0, 2 => -10.0, 1 => 10.0, 0 => 15.0 };
+        let q = polynomial! { 2 => -5.0, 1 => -1.0, 0 => 30.0 };
+        let r = polynomial! { 1 => -100.0, 0 => 30.0 };
+        let path = std::env::temp_dir().join("plot_styled_test");
+        let path = path.to_str().unwrap();
+        assert_eq!(
+            Polynomial::plot_styled(
+                &[
+                    (&p, "cubic", "red"),
+                    (&q, "", "dark-green"),
+                    (&r, "line", "#9400d3")
+                ],
+                -13.0,
+                5.0,
+                50,
+                path
+            ),
+            Ok(())
+        );
+        // The script embeds the plotted data as binary, so it is searched as bytes
+        let script_path = format!("{}.gnuplot", path);
+        let script = std::fs::read(&script_path).unwrap();
+        std::fs::remove_file(&script_path).unwrap();
+        let contains = |text: &str| {
+            script
+                .windows(text.len())
+                .any(|window| window == text.as_bytes())
+        };
+        for color in ["red", "dark-green", "#9400d3"].iter() {
+            assert!(contains(color));
+        }
+        // Empty caption falls back to the same caption as plot
+        assert!(contains(&q.to_latex()));
+        assert_eq!(
+            Polynomial::plot_styled(&[(&p, "", "red")], -13.0, 5.0, 1, "should_not_exist"),
+            Err("Requested less than 2 samples for plotting.")
+        );
     }
 
     #[test]
@@ -68,6 +228,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn numerical_derivative() {
+        let p = polynomial! { 3 => 1.0 };
+        let analytic = p.derivative().at(2.0);
+        assert_eq!(analytic, 12.0);
+        for &h in [0.1f32, 0.05, 0.01].iter() {
+            assert!((p.numerical_derivative(2.0, h) - analytic).abs() <= 2.0 * h * h);
+        }
+    }
+
     #[test]
     fn integral() {
         assert_eq!(Polynomial::new().integral(-5.0), polynomial! { 0 => -5.0 });
@@ -327,4 +497,1917 @@ mod tests {
         let q = Polynomial::new();
         let _ = &p % &q;
     }
+
+    #[test]
+    fn refine_all_coefficients_against() {
+        let truth = |x: f64| 2.0 * x.powi(3) - x + 0.5;
+        let samples = (0..=16)
+            .map(|i| -2.0 + 0.25 * i as f64)
+            .map(|x| (x, truth(x)))
+            .collect::<Vec<(f64, f64)>>();
+        let lossy = polynomial! { 3 => 2.0001, 2 => 0.0003, 1 => -0.9998, 0 => 0.5002 };
+        let refined = lossy.refine_all_coefficients_against(&samples);
+        let expected = [0.5f32, -1.0, 0.0, 2.0];
+        for (power, &coeff) in expected.iter().enumerate() {
+            let refined_coeff = refined.coeff_of_power.get(&power).cloned().unwrap_or(0.0);
+            assert!((refined_coeff - coeff).abs() < 1e-5);
+        }
+        assert_eq!(
+            Polynomial::new().refine_all_coefficients_against(&samples),
+            Polynomial::new()
+        );
+        assert_eq!(lossy.refine_all_coefficients_against(&samples[..2]), lossy);
+    }
+
+    #[test]
+    fn plot_ascii() {
+        let p = polynomial! { 1 => 1.0 };
+        let graph = p.plot_ascii(-1.0, 1.0, 21, 11);
+        let lines = graph.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 11);
+        assert!(lines.iter().all(|line| line.chars().count() == 21));
+        assert_eq!(lines[0].chars().last(), Some('*'));
+        assert_eq!(lines[10].chars().next(), Some('*'));
+        assert_eq!(lines[5].chars().nth(10), Some('*'));
+        assert_eq!(graph.matches('*').count(), 21);
+        assert_eq!(p.plot_ascii(-1.0, 1.0, 0, 11), "");
+        let constant = polynomial! { 0 => 2.0 }.plot_ascii(-1.0, 1.0, 5, 3);
+        assert_eq!(constant.lines().count(), 3);
+        assert_eq!(constant.matches('*').count(), 5);
+    }
+
+    #[test]
+    fn display_with_precision() {
+        assert_eq!(polynomial! { 0 => 2.345 }.display_with_precision(1), "2.3");
+        assert_eq!(
+            polynomial! { 2 => 1.0, 1 => -5.0, 0 => 6.0 }.display_with_precision(2),
+            "x^{2} - 5.00x + 6.00"
+        );
+        assert_eq!(
+            polynomial! { 3 => -1.0, 1 => 0.001, 0 => -2.25 }.display_with_precision(1),
+            "-x^{3} - 2.2"
+        );
+        assert_eq!(Polynomial::new().display_with_precision(3), "0");
+    }
+
+    #[test]
+    fn degree_histogram() {
+        let zero = Polynomial::new();
+        let constant = polynomial! { 0 => 3.0 };
+        let line = polynomial! { 1 => 1.0, 0 => 2.0 };
+        let other_line = polynomial! { 1 => -4.0 };
+        let quartic = polynomial! { 4 => 1.0, 2 => 1.0 };
+        let histogram =
+            crate::degree_histogram(&[&zero, &line, &constant, &quartic, &other_line, &zero]);
+        assert_eq!(
+            histogram
+                .into_iter()
+                .collect::<Vec<(Option<usize>, usize)>>(),
+            vec![(None, 2), (Some(0), 1), (Some(1), 2), (Some(4), 1)]
+        );
+        assert!(crate::degree_histogram(&[]).is_empty());
+    }
+
+    #[test]
+    fn rem_clean() {
+        let p = polynomial! { 2 => 0.1, 1 => 0.7, 0 => 0.3 };
+        let q = polynomial! { 1 => 1.5, 0 => 0.9 };
+        assert!((&p % &q).degree() < q.degree());
+        let r = p.rem_clean(&q, 1e-6);
+        assert_eq!(r.degree(), Some(0));
+        assert!((r.at(0.0) - -0.084).abs() < 1e-5);
+        assert_eq!(p.div_rem_clean(&q, 1e-6), (&p / &q, r));
+        assert_eq!(
+            polynomial! { 2 => 1.0, 0 => 1e-7 }.rem_clean(&polynomial! { 1 => 1.0 }, 1e-6),
+            Polynomial::new()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn rem_clean_with_zero_polynomial() {
+        let _ = polynomial! { 1 => 1.0 }.rem_clean(&Polynomial::new(), 1e-6);
+    }
+
+    #[test]
+    fn sample_log_grid() {
+        let p = polynomial! { 2 => 1.0, 0 => 1.0 };
+        let (xs, ys) = p.sample_log_grid(0.01, 100.0, 5).unwrap();
+        assert_eq!(xs.len(), 5);
+        assert_eq!(ys.len(), 5);
+        assert!((xs[0] - 0.01).abs() < 1e-6);
+        assert!((xs[4] - 100.0).abs() < 1e-3);
+        for window in xs.windows(2) {
+            assert!((window[1] / window[0] - 10.0).abs() < 1e-3);
+        }
+        assert!(xs.iter().zip(ys.iter()).all(|(&x, &y)| p.at(x) == y));
+        assert_eq!(
+            p.sample_log_grid(0.0, 1.0, 10),
+            Err(SampleError::NonPositiveBound)
+        );
+        assert_eq!(
+            p.sample_log_grid(-1.0, 1.0, 10),
+            Err(SampleError::NonPositiveBound)
+        );
+        assert_eq!(
+            p.sample_log_grid(2.0, 1.0, 10),
+            Err(SampleError::EmptyRange)
+        );
+        assert_eq!(
+            p.sample_log_grid(1.0, 2.0, 1),
+            Err(SampleError::TooFewSamples)
+        );
+    }
+
+    #[test]
+    fn continued_fraction() {
+        let p = polynomial! { 3 => 1.0, 1 => 2.0, 0 => 1.0 };
+        let q = polynomial! { 2 => 1.0, 0 => 1.0 };
+        let quotients = p.continued_fraction(&q);
+        assert_eq!(
+            quotients,
+            vec![
+                polynomial! { 1 => 1.0 },
+                polynomial! { 1 => 1.0, 0 => -1.0 },
+                polynomial! { 1 => 0.5, 0 => 0.5 },
+            ]
+        );
+        for &x in [0.5f32, 2.0, 3.0, -4.0].iter() {
+            let reconstructed = quotients
+                .iter()
+                .rev()
+                .fold(None, |tail: Option<f32>, quotient| match tail {
+                    Some(tail) => Some(quotient.at(x) + 1.0 / tail),
+                    None => Some(quotient.at(x)),
+                })
+                .unwrap();
+            assert!((reconstructed - p.at(x) / q.at(x)).abs() < 1e-5);
+        }
+        assert_eq!(
+            polynomial! { 2 => 1.0, 0 => 1.0 }.continued_fraction(&polynomial! { 1 => 1.0 }),
+            vec![polynomial! { 1 => 1.0 }, polynomial! { 1 => 1.0 }]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn continued_fraction_with_zero_polynomial() {
+        let _ = polynomial! { 1 => 1.0 }.continued_fraction(&Polynomial::new());
+    }
+
+    #[test]
+    fn pade() {
+        let exp_series = polynomial! { 2 => 0.5, 1 => 1.0, 0 => 1.0 };
+        assert_eq!(
+            Polynomial::pade(&exp_series, 1, 1),
+            Ok((
+                polynomial! { 1 => 0.5, 0 => 1.0 },
+                polynomial! { 1 => -0.5, 0 => 1.0 }
+            ))
+        );
+        let exp_series =
+            polynomial! { 4 => 1.0 / 24.0, 3 => 1.0 / 6.0, 2 => 0.5, 1 => 1.0, 0 => 1.0 };
+        let (p, q) = Polynomial::pade(&exp_series, 2, 2).unwrap();
+        for &x in [-0.5f32, 0.1, 0.5].iter() {
+            assert!((p.at(x) / q.at(x) - x.exp()).abs() < 1e-3);
+        }
+        assert_eq!(
+            Polynomial::pade(&exp_series, 4, 0),
+            Ok((exp_series.clone(), polynomial! { 0 => 1.0 }))
+        );
+        assert_eq!(
+            Polynomial::pade(&polynomial! { 2 => 1.0, 0 => 1.0 }, 1, 1),
+            Err(PadeError::Singular)
+        );
+    }
+
+    #[test]
+    fn convolve_blocked() {
+        let signal = polynomial! { 40 => 2.0, 17 => -1.0, 9 => 3.0, 8 => 1.0, 3 => -5.0, 0 => 4.0 };
+        let kernel = polynomial! { 3 => 1.0, 2 => -2.0, 0 => 7.0 };
+        for &block_size in [1, 3, 8, 64].iter() {
+            assert_eq!(
+                signal.convolve_blocked(&kernel, block_size),
+                &signal * &kernel
+            );
+            assert_eq!(
+                kernel.convolve_blocked(&signal, block_size),
+                &kernel * &signal
+            );
+        }
+        let long_signal = (0..=2000).fold(Polynomial::new(), |mut signal, power| {
+            signal.insert(power, (power % 7) as f32 - 3.0);
+            signal
+        });
+        let long_kernel = (0..16).fold(Polynomial::new(), |mut kernel, power| {
+            kernel.insert(power, (power % 3) as f32 + 1.0);
+            kernel
+        });
+        assert_eq!(
+            long_signal.convolve_blocked(&long_kernel, 256),
+            &long_signal * &long_kernel
+        );
+        assert_eq!(
+            Polynomial::new().convolve_blocked(&kernel, 4),
+            Polynomial::new()
+        );
+        assert_eq!(
+            signal.convolve_blocked(&Polynomial::new(), 4),
+            Polynomial::new()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn convolve_blocked_with_zero_block_size() {
+        let p = polynomial! { 1 => 1.0 };
+        let _ = p.convolve_blocked(&p, 0);
+    }
+
+    #[test]
+    fn norms() {
+        let p = polynomial! { 2 => 3.0, 1 => -4.0 };
+        assert_eq!(p.l1_norm(), 7.0);
+        assert_eq!(p.l2_norm(), 5.0);
+        assert_eq!(p.max_norm(), 4.0);
+        let zero = Polynomial::new();
+        assert_eq!(zero.l1_norm(), 0.0);
+        assert_eq!(zero.l2_norm(), 0.0);
+        assert_eq!(zero.max_norm(), 0.0);
+    }
+
+    #[test]
+    fn normalize_l2() {
+        let p = polynomial! { 5 => 1.5, 2 => -7.0, 0 => 0.25 };
+        let normalized = p.normalize_l2().unwrap();
+        assert!((normalized.l2_norm() - 1.0).abs() < 1e-6);
+        assert_eq!(normalized.degree(), p.degree());
+        assert_eq!(
+            polynomial! { 2 => 3.0, 1 => -4.0 }.normalize_l2(),
+            Some(polynomial! { 2 => 0.6, 1 => -0.8 })
+        );
+        assert_eq!(Polynomial::new().normalize_l2(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn perturb() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let p = polynomial! { 4 => 1.0, 3 => -10.0, 2 => 35.0, 1 => -50.0, 0 => 24.0 };
+        assert_eq!(p.perturb(0.0, &mut rng), p);
+        for _ in 0..100 {
+            let perturbed = p.perturb(1e-3, &mut rng);
+            assert_eq!(perturbed.degree(), p.degree());
+            for (power, coeff) in p.coeff_of_power.iter() {
+                let perturbed_coeff = perturbed.coeff_of_power[power];
+                assert!((perturbed_coeff - coeff).abs() <= 1e-3 * coeff.abs() * 1.001);
+            }
+        }
+        assert_eq!(Polynomial::new().perturb(0.5, &mut rng), Polynomial::new());
+    }
+
+    #[test]
+    fn sum_and_product_of_roots() {
+        let p = polynomial! { 2 => 1.0, 1 => -5.0, 0 => 6.0 };
+        assert_eq!(p.sum_of_roots(), Some(5.0));
+        assert_eq!(p.product_of_roots(), Some(6.0));
+        // 2(x - 1)(x - 2)(x + 4)
+        let p = polynomial! { 3 => 2.0, 2 => 2.0, 1 => -20.0, 0 => 16.0 };
+        assert_eq!(p.sum_of_roots(), Some(-1.0));
+        assert_eq!(p.product_of_roots(), Some(-8.0));
+        // x^2 + 1 has roots i and -i
+        let p = polynomial! { 2 => 1.0, 0 => 1.0 };
+        assert_eq!(p.sum_of_roots(), Some(0.0));
+        assert_eq!(p.product_of_roots(), Some(1.0));
+        assert_eq!(polynomial! { 0 => 3.0 }.sum_of_roots(), None);
+        assert_eq!(polynomial! { 0 => 3.0 }.product_of_roots(), None);
+        assert_eq!(Polynomial::new().sum_of_roots(), None);
+        assert_eq!(Polynomial::new().product_of_roots(), None);
+    }
+
+    #[test]
+    fn at_symmetric() {
+        let even = polynomial! { 8 => 0.5, 4 => -3.0, 2 => 1.0, 0 => 2.0 };
+        let odd = polynomial! { 7 => -0.5, 3 => 2.0, 1 => 4.0 };
+        let neither = polynomial! { 3 => 1.0, 2 => 1.0 };
+        assert!(even.is_even() && !even.is_odd());
+        assert!(odd.is_odd() && !odd.is_even());
+        assert!(!neither.is_even() && !neither.is_odd());
+        assert!(Polynomial::new().is_even() && Polynomial::new().is_odd());
+        for &x in [-2.5f32, -1.0, -0.3, 0.0, 0.7, 1.0, 1.9].iter() {
+            for p in [&even, &odd, &neither].iter() {
+                let expected = p.at(x);
+                assert!((p.at_symmetric(x) - expected).abs() <= 1e-5 * expected.abs().max(1.0));
+            }
+        }
+        assert_eq!(polynomial! { 4 => 1.0 }.at_symmetric(3.0), 81.0);
+        assert_eq!(polynomial! { 3 => 1.0 }.at_symmetric(-3.0), -27.0);
+        assert_eq!(Polynomial::new().at_symmetric(3.0), 0.0);
+    }
+
+    #[test]
+    fn piecewise_at() {
+        // |x| on [-1, 1] and x^2 on [1, 2]
+        let piecewise = PiecewisePolynomial {
+            breakpoints: vec![-1.0, 0.0, 1.0, 2.0],
+            pieces: vec![
+                polynomial! { 1 => -1.0 },
+                polynomial! { 1 => 1.0 },
+                polynomial! { 2 => 1.0 },
+            ],
+        };
+        assert_eq!(piecewise.at(-1.0), Some(1.0));
+        assert_eq!(piecewise.at(-0.5), Some(0.5));
+        assert_eq!(piecewise.at(0.0), Some(0.0));
+        assert_eq!(piecewise.at(0.5), Some(0.5));
+        assert_eq!(piecewise.at(1.0), Some(1.0));
+        assert_eq!(piecewise.at(1.5), Some(2.25));
+        assert_eq!(piecewise.at(2.0), Some(4.0));
+        assert_eq!(piecewise.at(-1.1), None);
+        assert_eq!(piecewise.at(2.1), None);
+        assert_eq!(piecewise.at(f32::NAN), None);
+        let empty = PiecewisePolynomial {
+            breakpoints: vec![],
+            pieces: vec![],
+        };
+        assert_eq!(empty.at(0.0), None);
+    }
+
+    #[test]
+    fn piecewise_continuity_defects() {
+        let continuous = PiecewisePolynomial {
+            breakpoints: vec![-1.0, 0.0, 1.0],
+            pieces: vec![polynomial! { 1 => -1.0 }, polynomial! { 1 => 1.0 }],
+        };
+        assert_eq!(continuous.continuity_defects(), vec![0.0]);
+        let step = PiecewisePolynomial {
+            breakpoints: vec![0.0, 1.0, 2.0, 3.0],
+            pieces: vec![
+                polynomial! { 0 => 1.0 },
+                polynomial! { 1 => 1.0 },
+                polynomial! { 0 => 5.0 },
+            ],
+        };
+        assert_eq!(step.continuity_defects(), vec![0.0, 3.0]);
+    }
+
+    #[test]
+    fn from_taylor_coeffs() {
+        assert_eq!(
+            Polynomial::from_taylor_coeffs(2.0, &[1.0, -1.0, 3.0, 0.5]),
+            // 1 - (x - 2) + 3(x - 2)^2 + 0.5(x - 2)^3
+            polynomial! { 3 => 0.5, 1 => -7.0, 0 => 11.0 }
+        );
+        assert_eq!(Polynomial::from_taylor_coeffs(2.0, &[]), Polynomial::new());
+        assert_eq!(
+            Polynomial::from_taylor_coeffs(-1.0, &[4.0]),
+            polynomial! { 0 => 4.0 }
+        );
+    }
+
+    #[test]
+    fn natural_cubic_spline() {
+        let points = [(0.0, 1.0), (1.0, 3.0), (2.5, 2.0), (3.0, -1.0), (4.5, 0.5)];
+        let spline = Polynomial::natural_cubic_spline(&points).unwrap();
+        assert_eq!(spline.pieces.len(), points.len() - 1);
+        assert!(spline.pieces.iter().all(|piece| piece.degree() <= Some(3)));
+        for &(x, y) in points.iter() {
+            assert!((spline.at(x).unwrap() - y).abs() < 1e-4);
+        }
+        for (i, pair) in spline.pieces.windows(2).enumerate() {
+            let knot = points[i + 1].0;
+            let (left, right) = (&pair[0], &pair[1]);
+            assert!((left.at(knot) - right.at(knot)).abs() < 1e-4);
+            assert!((left.derivative().at(knot) - right.derivative().at(knot)).abs() < 1e-3);
+            assert!(
+                (left.derivative().derivative().at(knot)
+                    - right.derivative().derivative().at(knot))
+                .abs()
+                    < 1e-3
+            );
+        }
+        let first = &spline.pieces[0];
+        let last = &spline.pieces[spline.pieces.len() - 1];
+        assert!(first.derivative().derivative().at(0.0).abs() < 1e-3);
+        assert!(last.derivative().derivative().at(4.5).abs() < 1e-3);
+        // Two points give the line through them
+        let line = Polynomial::natural_cubic_spline(&[(1.0, 1.0), (3.0, 5.0)]).unwrap();
+        assert_eq!(line.pieces, vec![polynomial! { 1 => 2.0, 0 => -1.0 }]);
+        assert_eq!(
+            Polynomial::natural_cubic_spline(&[(1.0, 1.0)]),
+            Err(InterpError::TooFewPoints)
+        );
+        assert_eq!(
+            Polynomial::natural_cubic_spline(&[(1.0, 1.0), (0.0, 1.0), (2.0, 1.0)]),
+            Err(InterpError::NotStrictlyIncreasing)
+        );
+        assert_eq!(
+            Polynomial::natural_cubic_spline(&[(0.0, 1.0), (1.0, 1.0), (1.0, 2.0)]),
+            Err(InterpError::NotStrictlyIncreasing)
+        );
+    }
+
+    #[test]
+    fn hermite() {
+        let points = [(0.0, 1.0, -2.0), (1.5, 0.5, 3.0)];
+        let p = Polynomial::hermite(&points).unwrap();
+        assert!(p.degree() <= Some(3));
+        for &(x, f, slope) in points.iter() {
+            assert!((p.at(x) - f).abs() < 1e-4);
+            assert!((p.derivative().at(x) - slope).abs() < 1e-4);
+        }
+        // Values and slopes of x^3 - 2x at 3 points recover it
+        let cubic = polynomial! { 3 => 1.0, 1 => -2.0 };
+        let points = [-1.0f32, 2.0, 0.5]
+            .iter()
+            .map(|&x| (x, cubic.at(x), cubic.derivative().at(x)))
+            .collect::<Vec<(f32, f32, f32)>>();
+        let p = Polynomial::hermite(&points).unwrap();
+        for power in 0..=5 {
+            let expected = cubic.coeff_of_power.get(&power).cloned().unwrap_or(0.0);
+            let actual = p.coeff_of_power.get(&power).cloned().unwrap_or(0.0);
+            assert!((expected - actual).abs() < 1e-4);
+        }
+        assert_eq!(
+            Polynomial::hermite(&[(2.0, 3.0, 0.5)]),
+            Ok(polynomial! { 1 => 0.5, 0 => 2.0 })
+        );
+        assert_eq!(Polynomial::hermite(&[]), Err(InterpError::TooFewPoints));
+        assert_eq!(
+            Polynomial::hermite(&[(1.0, 1.0, 1.0), (0.0, 0.0, 0.0), (1.0, 2.0, 0.0)]),
+            Err(InterpError::DuplicateX)
+        );
+    }
+
+    #[test]
+    fn orthogonal_basis() {
+        let basis = Polynomial::orthogonal_basis(4, |_| 1.0, -1.0, 1.0, 401);
+        assert_eq!(basis.len(), 5);
+        for (k, element) in basis.iter().enumerate() {
+            assert_eq!(element.degree(), Some(k));
+            assert_eq!(element.coeff_of_power[&k], 1.0);
+        }
+        for i in 0..basis.len() {
+            for j in 0..i {
+                let inner =
+                    Polynomial::inner_product(&basis[i], &basis[j], &|_| 1.0, -1.0, 1.0, 401);
+                assert!(inner.abs() < 1e-5);
+            }
+        }
+        // Monic Legendre polynomials
+        assert_eq!(basis[0], polynomial! { 0 => 1.0 });
+        assert!(
+            basis[1]
+                .coeff_of_power
+                .get(&0)
+                .cloned()
+                .unwrap_or(0.0)
+                .abs()
+                < 1e-6
+        );
+        assert!((basis[2].coeff_of_power[&0] + 1.0 / 3.0).abs() < 1e-5);
+        assert!((basis[3].coeff_of_power[&1] + 3.0 / 5.0).abs() < 1e-5);
+        // Orthogonality under a non trivial weight
+        let weight = |x: f32| 1.0 + x * x;
+        let basis = Polynomial::orthogonal_basis(3, weight, 0.0, 2.0, 201);
+        for i in 0..basis.len() {
+            for j in 0..i {
+                let inner = Polynomial::inner_product(&basis[i], &basis[j], &weight, 0.0, 2.0, 201);
+                assert!(inner.abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn project() {
+        let basis = Polynomial::orthogonal_basis(3, |_| 1.0, -1.0, 1.0, 401);
+        let expected = [2.0f32, 0.0, -3.0, 0.5];
+        let p = basis
+            .iter()
+            .zip(expected.iter())
+            .fold(Polynomial::new(), |sum, (element, &c)| {
+                &sum + &(element * &polynomial! { 0 => c })
+            });
+        let coeffs = p.project(&basis, |_| 1.0, -1.0, 1.0, 401);
+        assert_eq!(coeffs.len(), expected.len());
+        for (&actual, &expected) in coeffs.iter().zip(expected.iter()) {
+            assert!((actual - expected).abs() < 1e-4);
+        }
+        let basis = Polynomial::orthogonal_basis(2, |x| x.exp(), 0.0, 1.0, 201);
+        let coeffs = basis[1].project(&basis, |x| x.exp(), 0.0, 1.0, 201);
+        assert!(coeffs[0].abs() < 1e-4 && (coeffs[1] - 1.0).abs() < 1e-4 && coeffs[2].abs() < 1e-4);
+        assert!(p.project(&[], |_| 1.0, -1.0, 1.0, 401).is_empty());
+    }
+
+    #[test]
+    fn remez() {
+        let degree = 3;
+        let p = Polynomial::remez(|x| x.exp(), degree, -1.0, 1.0, 20);
+        assert_eq!(p.degree(), Some(degree));
+        let errors = (0..=4000)
+            .map(|i| -1.0 + i as f32 / 2000.0)
+            .map(|x| (x as f64).exp() - p.at(x) as f64)
+            .collect::<Vec<f64>>();
+        let max_error = errors.iter().fold(0f64, |max, e| max.max(e.abs()));
+        // Known minimax error of a cubic approximation to exp on [-1, 1]
+        assert!((max_error - 0.005529).abs() < 1e-5);
+        // Error equioscillates at degree + 2 points
+        let mut alternations = 0;
+        let mut last_sign = 0.0;
+        for &e in errors.iter().filter(|e| e.abs() > 0.99 * max_error) {
+            if e.signum() != last_sign {
+                alternations += 1;
+                last_sign = e.signum();
+            }
+        }
+        assert_eq!(alternations, degree + 2);
+        // Off center interval
+        let p = Polynomial::remez(|x| x.sin(), 4, 1.0, 3.0, 20);
+        let max_error = (0..=2000)
+            .map(|i| 1.0 + i as f32 / 1000.0)
+            .map(|x| ((x as f64).sin() - p.at(x) as f64).abs())
+            .fold(0f64, f64::max);
+        assert!(max_error < 1e-3);
+        // A polynomial target is reproduced
+        let p = Polynomial::remez(|x| 2.0 * x * x - x + 3.0, 2, -2.0, 5.0, 10);
+        for (power, &expected) in [3.0f32, -1.0, 2.0].iter().enumerate() {
+            assert!((p.coeff_of_power[&power] - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn isolate_real_roots() {
+        fn assert_isolating(intervals: &[(f32, f32)], roots: &[f64]) {
+            assert_eq!(intervals.len(), roots.len());
+            for pair in intervals.windows(2) {
+                assert!(pair[0].1 <= pair[1].0);
+            }
+            for &(lo, hi) in intervals.iter() {
+                let (lo, hi) = (lo as f64, hi as f64);
+                let contained = roots
+                    .iter()
+                    .filter(|&&root| lo < root && root < hi || (root - lo).abs() < 1e-6 && lo == hi)
+                    .count();
+                assert_eq!(contained, 1);
+            }
+        }
+        // (x - 1)(x - 1.001), roots of the f32 coefficients are about 1.00014 and 1.00086
+        let p = polynomial! { 2 => 1.0, 1 => -2.001, 0 => 1.001 };
+        let (b, c) = (-2.001f32 as f64, 1.001f32 as f64);
+        let discriminant = (b * b - 4.0 * c).sqrt();
+        assert_isolating(
+            &p.isolate_real_roots().unwrap(),
+            &[(-b - discriminant) / 2.0, (-b + discriminant) / 2.0],
+        );
+        // x(x - 1)(x - 2)(x + 3)
+        let p = polynomial! { 4 => 1.0, 2 => -7.0, 1 => 6.0 };
+        assert_isolating(&p.isolate_real_roots().unwrap(), &[-3.0, 0.0, 1.0, 2.0]);
+        // (x - 1)^2 (x - 2) has 2 distinct roots
+        let p = polynomial! { 3 => 1.0, 2 => -4.0, 1 => 5.0, 0 => -2.0 };
+        assert_isolating(&p.isolate_real_roots().unwrap(), &[1.0, 2.0]);
+        // (x^2 + 1)(x - 5)
+        let p = polynomial! { 3 => 1.0, 2 => -5.0, 1 => 1.0, 0 => -5.0 };
+        assert_isolating(&p.isolate_real_roots().unwrap(), &[5.0]);
+        // (x - 1)(x - 2)(x - 3)(x - 4)(x + 0.5)
+        let p = polynomial! { 5 => 1.0, 4 => -9.5, 3 => 30.0, 2 => -32.5, 1 => -1.0, 0 => 12.0 };
+        assert_isolating(
+            &p.isolate_real_roots().unwrap(),
+            &[-0.5, 1.0, 2.0, 3.0, 4.0],
+        );
+        assert_eq!(
+            polynomial! { 2 => 1.0, 0 => 1.0 }.isolate_real_roots(),
+            Some(vec![])
+        );
+        assert_eq!(polynomial! { 0 => 3.0 }.isolate_real_roots(), Some(vec![]));
+        assert_eq!(Polynomial::new().isolate_real_roots(), Some(vec![]));
+        // Sign variations with a NaN coefficient never settle, so the subdivision runs out of steps
+        let p = polynomial! { 2 => 1.0, 1 => -3.0, 0 => f32::NAN };
+        assert_eq!(p.isolate_real_roots(), None);
+        assert_eq!(p.isolate_positive_roots(), None);
+    }
+
+    #[test]
+    fn dense_helpers() {
+        use crate::dense;
+        // x^2 - 3x + 2 = (x - 1)(x - 2)
+        let p = [2.0, -3.0, 1.0];
+        assert_eq!(dense::sign_variations(&p), 2);
+        assert_eq!(dense::sign_variations(&[1.0, 0.0, 1.0]), 0);
+        assert_eq!(dense::taylor_shift(&p, 1.0), vec![0.0, -1.0, 1.0]);
+        assert_eq!(dense::reverse(&[0.0, 2.0, 1.0]), vec![1.0, 2.0]);
+        assert_eq!(dense::reflect(&p), vec![2.0, 3.0, 1.0]);
+        assert_eq!(dense::derivative(&p), vec![-3.0, 2.0]);
+        assert_eq!(
+            dense::div_rem(&p, &[-1.0, 1.0], 0.0),
+            (vec![-2.0, 1.0], vec![])
+        );
+        assert_eq!(dense::gcd(&p, &[-3.0, 2.0, 1.0]), vec![-1.0, 1.0]);
+        // (x - 1)^2 (x - 2)
+        assert_eq!(dense::square_free(&[-2.0, 5.0, -4.0, 1.0]), p.to_vec());
+        assert!(dense::positive_root_upper_bound(&p) >= 2.0);
+        assert_eq!(dense::positive_root_upper_bound(&[1.0, 1.0]), 0.0);
+    }
+
+    #[test]
+    fn evaluate_many_into() {
+        let p = polynomial! { 2 => 1.0, 1 => -5.0, 0 => 6.0 };
+        let xs = [-1.0, 0.0, 2.5, 3.0];
+        let mut out = [f32::NAN; 4];
+        assert_eq!(p.evaluate_many_into(&xs, &mut out), Ok(()));
+        assert_eq!(out.to_vec(), p.evaluate_many(&xs));
+        let mut short = [0.0; 3];
+        assert_eq!(
+            p.evaluate_many_into(&xs, &mut short),
+            Err(LenMismatch {
+                expected: 4,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn compose_mod() {
+        let p = polynomial! { 3 => 1.0, 1 => -2.0, 0 => 1.0 };
+        let inner = polynomial! { 1 => 1.0, 0 => 1.0 };
+        let modulus = polynomial! { 2 => 1.0, 0 => 1.0 };
+        // p(x + 1) = x^3 + 3x^2 + x
+        let composed = polynomial! { 3 => 1.0, 2 => 3.0, 1 => 1.0 };
+        assert_eq!(p.compose_mod(&inner, &modulus), &composed % &modulus);
+        assert_eq!(p.compose_mod(&inner, &modulus), polynomial! { 0 => -3.0 });
+        assert_eq!(
+            Polynomial::new().compose_mod(&inner, &modulus),
+            Polynomial::new()
+        );
+    }
+
+    #[test]
+    fn pow_mod() {
+        let p = polynomial! { 1 => 1.0, 0 => 1.0 };
+        let modulus = polynomial! { 3 => 1.0, 1 => -1.0, 0 => 2.0 };
+        let mut power = polynomial! { 0 => 1.0 };
+        for exp in 0..6 {
+            assert_eq!(p.pow_mod(exp, &modulus), &power % &modulus);
+            power = &power * &p;
+        }
+        assert_eq!(
+            p.mul_mod(&p, &modulus),
+            polynomial! { 2 => 1.0, 1 => 2.0, 0 => 1.0 }
+        );
+        // Everything is zero modulo a constant
+        assert_eq!(p.pow_mod(3, &polynomial! { 0 => 2.0 }), Polynomial::new());
+    }
+
+    #[test]
+    fn recurrence_nth_term() {
+        // a(n + 2) = a(n + 1) + 2 a(n)
+        let characteristic = polynomial! { 2 => 1.0, 1 => -1.0, 0 => -2.0 };
+        let mut terms = vec![1.0, 3.0];
+        for n in 2..20 {
+            terms.push(terms[n - 1] + 2.0 * terms[n - 2]);
+        }
+        for (n, &term) in terms.iter().enumerate() {
+            let computed = characteristic.recurrence_nth_term(&[1.0, 3.0], n).unwrap();
+            assert!((computed - term).abs() <= term.abs() * 1e-5);
+        }
+        assert_eq!(characteristic.recurrence_nth_term(&[1.0], 5), None);
+        assert_eq!(Polynomial::new().recurrence_nth_term(&[], 5), None);
+    }
+
+    #[test]
+    fn to_rust_fn_source() {
+        let p = polynomial! { 3 => 2.0, 1 => -5.5, 0 => 6.0 };
+        assert_eq!(
+            p.to_rust_fn_source("cubic"),
+            "fn cubic(x: f32) -> f32 {\n    let mut y = 2.0f32;\n    y = y * x + 0.0f32;\n    y = y * x + -5.5f32;\n    y = y * x + 6.0f32;\n    y\n}\n"
+        );
+        assert_eq!(
+            polynomial! { 0 => 1.5 }.to_rust_fn_source("c"),
+            "fn c(_x: f32) -> f32 {\n    1.5f32\n}\n"
+        );
+        assert!(Polynomial::new()
+            .to_rust_fn_source("zero")
+            .starts_with("fn zero(_x: f32) -> f32 {"));
+    }
+
+    #[test]
+    fn checksum() {
+        let p = polynomial! { 2 => 1.0, 1 => -5.0, 0 => 6.0 };
+        let mut q = polynomial! { 0 => 6.0, 5 => 3.0 };
+        q.insert(2, 1.0);
+        q.insert(1, -5.0);
+        q.insert(5, 0.0);
+        assert_eq!(p, q);
+        assert_eq!(p.checksum(), q.checksum());
+        assert_ne!(
+            p.checksum(),
+            polynomial! { 2 => 1.0, 1 => -5.0, 0 => 6.5 }.checksum()
+        );
+        assert_ne!(
+            p.checksum(),
+            polynomial! { 3 => 1.0, 1 => -5.0, 0 => 6.0 }.checksum()
+        );
+        assert_ne!(p.checksum(), Polynomial::new().checksum());
+        assert_eq!(Polynomial::new().checksum(), 0xcbf29ce484222325);
+    }
+
+    #[test]
+    fn newton_fraction() {
+        let p = polynomial! { 3 => 1.0, 2 => -2.0, 0 => 5.0 };
+        for &x in [-2.0, -0.5, 0.75, 3.0].iter() {
+            let (value, slope) = p.evaluate_with_derivative(x);
+            assert!((value - p.at(x)).abs() <= 1e-5);
+            assert!((slope - p.derivative().at(x)).abs() <= 1e-5);
+            let expected = p.at(x) / p.derivative().at(x);
+            assert!((p.newton_fraction(x).unwrap() - expected).abs() <= 1e-5 * expected.abs());
+        }
+        // p'(x) = 3x^2 - 4x vanishes at 0
+        assert_eq!(p.newton_fraction(0.0), None);
+        assert_eq!(Polynomial::new().evaluate_with_derivative(2.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn unit_leading() {
+        assert_eq!(
+            polynomial! { 2 => -2.0, 1 => 1.0 }.unit_leading(),
+            Some(polynomial! { 2 => -1.0, 1 => 0.5 })
+        );
+        assert_eq!(
+            polynomial! { 3 => 4.0, 0 => -2.0 }.unit_leading(),
+            Some(polynomial! { 3 => 1.0, 0 => -0.5 })
+        );
+        assert_eq!(Polynomial::new().unit_leading(), None);
+    }
+
+    #[test]
+    fn estimate_degree_from_samples() {
+        let cube = (0..10)
+            .map(|i| {
+                let x = -1.0 + i as f32 * 0.25;
+                (x, x * x * x)
+            })
+            .collect::<Vec<(f32, f32)>>();
+        assert_eq!(
+            Polynomial::estimate_degree_from_samples(&cube, 1e-4),
+            Some(3)
+        );
+        let constant = [(0.0, 2.0), (1.0, 2.0), (2.0, 2.0)];
+        assert_eq!(
+            Polynomial::estimate_degree_from_samples(&constant, 0.0),
+            Some(0)
+        );
+        // Too few samples to see the cubic term become constant
+        assert_eq!(
+            Polynomial::estimate_degree_from_samples(&cube[..4], 1e-4),
+            None
+        );
+        let uneven = [(0.0, 0.0), (1.0, 1.0), (3.0, 9.0), (4.0, 16.0)];
+        assert_eq!(
+            Polynomial::estimate_degree_from_samples(&uneven, 1e-4),
+            None
+        );
+        assert_eq!(
+            Polynomial::estimate_degree_from_samples(&cube[..1], 1e-4),
+            None
+        );
+    }
+
+    #[test]
+    fn root_sensitivity() {
+        // wilkinson(8) = (x - 1)(x - 2)...(x - 8)
+        let mut wilkinson = polynomial! { 0 => 1.0 };
+        for k in 1..=8 {
+            wilkinson = &wilkinson * &polynomial! { 1 => 1.0, 0 => -(k as f32) };
+        }
+        let sensitivities = wilkinson.root_sensitivity();
+        assert_eq!(sensitivities.len(), 8);
+        for (k, &(re, im, _)) in sensitivities.iter().enumerate() {
+            assert!((re - (k + 1) as f64).abs() <= 1e-6);
+            assert!(im.abs() <= 1e-6);
+        }
+        // Exact values are 72 for root 1 and 12870 for root 8
+        assert!((sensitivities[0].2 - 72.0).abs() <= 1e-3);
+        assert!((sensitivities[7].2 - 12870.0).abs() <= 1.0);
+        let small = sensitivities[0].2.max(sensitivities[1].2);
+        assert!(sensitivities[4..].iter().all(|s| s.2 > 10.0 * small));
+        // Complex conjugate pair of x^2 + 1
+        let pair = polynomial! { 2 => 1.0, 0 => 1.0 }.root_sensitivity();
+        assert_eq!(pair.len(), 2);
+        assert!(pair[0].0.abs() <= 1e-12 && (pair[0].1 + 1.0).abs() <= 1e-12);
+        assert!((pair[1].1 - 1.0).abs() <= 1e-12 && (pair[1].2 - 1.0).abs() <= 1e-12);
+        assert!(polynomial! { 0 => 3.0 }.root_sensitivity().is_empty());
+    }
+
+    #[test]
+    fn to_transfer_function() {
+        let num = polynomial! { 0 => 1.0 };
+        let den = polynomial! { 1 => 1.0, 0 => 1.0 };
+        assert_eq!(
+            Polynomial::to_transfer_function(&num, &den, 's'),
+            "  1\n-----\ns + 1"
+        );
+        let num = polynomial! { 2 => 2.0, 1 => -1.0, 0 => 0.5 };
+        let den = polynomial! { 1 => -3.0 };
+        let lines = Polynomial::to_transfer_function(&num, &den, 'z')
+            .lines()
+            .map(String::from)
+            .collect::<Vec<String>>();
+        assert_eq!(lines, vec!["2z^2 - z + 0.5", "--------------", "     -3z"]);
+    }
+
+    #[test]
+    fn coefficient_via_derivative() {
+        let p = polynomial! { 5 => 0.5, 3 => -2.0, 2 => 7.0, 0 => -1.25 };
+        for k in 0..=7 {
+            let expected = p.coeff_of_power.get(&k).cloned().unwrap_or(0.0);
+            assert!((p.coefficient_via_derivative(k) - expected).abs() <= 1e-6);
+        }
+        let derivatives = p.all_derivatives_at(2.0);
+        assert_eq!(derivatives.len(), 6);
+        let mut q = p.clone();
+        for derivative in derivatives {
+            assert!((derivative - q.at(2.0)).abs() <= 1e-4 * q.at(2.0).abs().max(1.0));
+            q = q.derivative();
+        }
+        assert!(Polynomial::new().all_derivatives_at(1.0).is_empty());
+        // 40! overflows f32
+        let p = polynomial! { 40 => 1.5, 37 => -0.25, 35 => 2.0, 1 => 3.0 };
+        assert!(p.all_derivatives_at(0.0)[40].is_infinite());
+        for k in 0..=41 {
+            let expected = p.coeff_of_power.get(&k).cloned().unwrap_or(0.0);
+            assert!((p.coefficient_via_derivative(k) - expected).abs() <= 1e-6);
+        }
+    }
+
+    #[test]
+    fn balanced_companion_matrix() {
+        use crate::{dense, linalg};
+        let p = polynomial! { 4 => 1e-3, 3 => 2.0, 2 => -50.0, 1 => 1e3, 0 => 1e4 };
+        let balanced = p.balanced_companion_matrix().unwrap();
+        let unbalanced = dense::companion_matrix(&p.to_dense_f64());
+        let frobenius = |m: &[Vec<f64>]| m.iter().flatten().map(|x| x * x).sum::<f64>().sqrt();
+        assert!(frobenius(&balanced) < frobenius(&unbalanced) / 10.0);
+        let balanced = linalg::hessenberg_eigenvalues(&balanced).unwrap();
+        let unbalanced = linalg::hessenberg_eigenvalues(&unbalanced).unwrap();
+        assert_eq!(balanced.len(), 4);
+        for s in balanced {
+            let nearest = unbalanced
+                .iter()
+                .map(|&t| (s - t).norm() / t.norm())
+                .fold(f64::INFINITY, f64::min);
+            assert!(nearest <= 1e-12);
+        }
+        assert_eq!(polynomial! { 0 => 2.0 }.balanced_companion_matrix(), None);
+        assert_eq!(
+            polynomial! { 1 => 2.0, 0 => 1.0 }.balanced_companion_matrix(),
+            Some(vec![vec![-0.5]])
+        );
+    }
+
+    #[test]
+    fn require_max_degree() {
+        let p = polynomial! { 3 => 1.0, 0 => -1.0 };
+        assert_eq!(p.require_max_degree(3), Ok(()));
+        assert_eq!(p.require_max_degree(5), Ok(()));
+        assert_eq!(
+            p.require_max_degree(2),
+            Err(DegreeError { actual: 3, max: 2 })
+        );
+        assert_eq!(Polynomial::new().require_max_degree(0), Ok(()));
+    }
+
+    #[test]
+    fn forward_difference_table() {
+        let squares = (0..5)
+            .map(|i| (i as f32 * 0.5, (i as f32 * 0.5).powi(2)))
+            .collect::<Vec<(f32, f32)>>();
+        let table = Polynomial::forward_difference_table(&squares).unwrap();
+        assert_eq!(table.len(), 5);
+        assert_eq!(table[0], vec![0.0, 0.25, 1.0, 2.25, 4.0]);
+        assert_eq!(table[1], vec![0.25, 0.75, 1.25, 1.75]);
+        assert_eq!(table[2], vec![0.5, 0.5, 0.5]);
+        assert_eq!(table[3], vec![0.0, 0.0]);
+        assert_eq!(
+            Polynomial::newton_forward(&squares),
+            Ok(polynomial! { 2 => 1.0 })
+        );
+        let p = polynomial! { 3 => 2.0, 1 => -1.0, 0 => 3.0 };
+        let samples = (0..4)
+            .map(|i| (1.0 + i as f32, p.at(1.0 + i as f32)))
+            .collect::<Vec<(f32, f32)>>();
+        let interpolant = Polynomial::newton_forward(&samples).unwrap();
+        for x in [-1.0, 0.5, 2.5, 6.0].iter() {
+            assert!((interpolant.at(*x) - p.at(*x)).abs() <= 1e-3 * p.at(*x).abs().max(1.0));
+        }
+        assert_eq!(
+            Polynomial::forward_difference_table(&[(0.0, 1.0), (1.0, 2.0), (3.0, 4.0)]),
+            Err(InterpError::UnequalSpacing)
+        );
+        assert_eq!(
+            Polynomial::forward_difference_table(&[(1.0, 1.0), (0.0, 2.0)]),
+            Err(InterpError::NotStrictlyIncreasing)
+        );
+        assert_eq!(
+            Polynomial::forward_difference_table(&[]),
+            Err(InterpError::TooFewPoints)
+        );
+    }
+
+    #[test]
+    fn at_f64() {
+        // (x - 1)^5, which suffers from cancellation near 1
+        let p = polynomial! { 5 => 1.0, 4 => -5.0, 3 => 10.0, 2 => -10.0, 1 => 5.0, 0 => -1.0 };
+        let x = 1.0 + 1.0 / 128.0;
+        let reference = (x as f64 - 1.0).powi(5);
+        let error_f64 = (p.at_f64(x as f64) - reference).abs();
+        let error_f32 = (p.at_f32(x) as f64 - reference).abs();
+        assert!(error_f64 <= 1e-14);
+        assert!(error_f64 * 1e3 < error_f32);
+        assert_eq!(p.at_f32(2.0), 1.0);
+        assert_eq!(p.at_f32(x), p.at(x));
+        assert_eq!(Polynomial::new().at_f64(3.0), 0.0);
+    }
+
+    #[test]
+    fn macro_accumulates_duplicate_powers() {
+        assert_eq!(polynomial! { 2 => 1.0, 2 => 3.0 }, polynomial! { 2 => 4.0 });
+        assert_eq!(
+            polynomial! { 1 => 2.0, 0 => 1.0, 1 => -2.0 },
+            polynomial! { 0 => 1.0 }
+        );
+        let mut p = polynomial! { 3 => 1.0 };
+        p.add_term(3, 0.5);
+        p.add_term(0, -2.0);
+        assert_eq!(p, polynomial! { 3 => 1.5, 0 => -2.0 });
+        p.add_term(3, -1.5);
+        assert_eq!(p.degree(), Some(0));
+    }
+
+    #[test]
+    fn roots_in_rectangle() {
+        let p = polynomial! { 2 => 1.0, 0 => 1.0 };
+        let roots = p.roots_in_rectangle((-1.0, 1.5), (0.3, 2.0), 1e-6);
+        assert_eq!(roots.len(), 1);
+        assert!(roots[0].0.abs() <= 1e-6 && (roots[0].1 - 1.0).abs() <= 1e-6);
+        assert_eq!(
+            p.roots_in_rectangle((-2.0, 2.0), (-2.0, 2.0), 1e-6).len(),
+            2
+        );
+        assert!(p
+            .roots_in_rectangle((0.5, 2.0), (-2.0, 2.0), 1e-6)
+            .is_empty());
+        // Double root at 1 and a simple one at -2
+        let q = polynomial! { 3 => 1.0, 1 => -3.0, 0 => 2.0 };
+        let roots = q.roots_in_rectangle((-3.0, 3.0), (-1.0, 1.0), 1e-5);
+        assert_eq!(roots.len(), 3);
+        assert!((roots[0].0 + 2.0).abs() <= 1e-5);
+        assert!((roots[1].0 - 1.0).abs() <= 1e-5 && roots[1] == roots[2]);
+        assert!(polynomial! { 0 => 1.0 }
+            .roots_in_rectangle((-1.0, 1.0), (-1.0, 1.0), 1e-3)
+            .is_empty());
+    }
+
+    #[test]
+    fn root_jacobian() {
+        // x^2 - 3x + 2 = (x - 1)(x - 2)
+        let p = polynomial! { 2 => 1.0, 1 => -3.0, 0 => 2.0 };
+        let larger_root =
+            |a: [f64; 3]| (-a[1] + (a[1] * a[1] - 4.0 * a[2] * a[0]).sqrt()) / (2.0 * a[2]);
+        let jacobian = p.root_jacobian(2.0);
+        assert_eq!(jacobian.len(), 3);
+        let h = 1e-6;
+        for k in 0..3 {
+            let mut perturbed = [2.0, -3.0, 1.0];
+            perturbed[k] += h;
+            let finite_difference = (larger_root(perturbed) - 2.0) / h;
+            assert!((jacobian[k] - finite_difference).abs() <= 1e-4);
+        }
+        assert_eq!(jacobian, vec![-1.0, -2.0, -4.0]);
+        assert!(Polynomial::new().root_jacobian(1.0).is_empty());
+    }
+
+    #[test]
+    fn substitute_power() {
+        let p = polynomial! { 1 => 1.0, 0 => 1.0 };
+        assert_eq!(p.substitute_power(2), polynomial! { 2 => 1.0, 0 => 1.0 });
+        assert_eq!(p.substitute_power(1), p);
+        assert_eq!(p.substitute_power(0), polynomial! { 0 => 2.0 });
+        let q = polynomial! { 3 => -2.0, 1 => 0.5, 0 => 4.0 };
+        assert_eq!(
+            q.substitute_power(3),
+            polynomial! { 9 => -2.0, 3 => 0.5, 0 => 4.0 }
+        );
+        assert_eq!(Polynomial::new().substitute_power(0), Polynomial::new());
+    }
+
+    #[test]
+    fn with_nonzero_invariant_enforced() {
+        let imported = Polynomial {
+            coeff_of_power: vec![(0, 1.0), (1, 0.0), (2, -0.0), (3, 2.0), (7, 0.0)]
+                .into_iter()
+                .collect(),
+        };
+        let (cleaned, removed) = imported.with_nonzero_invariant_enforced();
+        assert_eq!(removed, 3);
+        assert_eq!(cleaned, polynomial! { 3 => 2.0, 0 => 1.0 });
+        assert_eq!(cleaned.degree(), Some(3));
+        assert_eq!(
+            cleaned.with_nonzero_invariant_enforced(),
+            (cleaned.clone(), 0)
+        );
+    }
+
+    #[test]
+    fn newton_polygon() {
+        // Roots near -1e-3 and -1e3
+        let p = polynomial! { 2 => 1.0, 1 => 1000.0, 0 => 1.0 };
+        let hull = p.newton_polygon();
+        assert_eq!(
+            hull.iter().map(|v| v.0).collect::<Vec<usize>>(),
+            vec![0, 1, 2]
+        );
+        assert!((hull[1].1 + 1000f32.ln()).abs() <= 1e-6);
+        let slopes = hull
+            .windows(2)
+            .map(|w| (w[1].1 - w[0].1) / (w[1].0 - w[0].0) as f32);
+        let magnitudes = slopes.map(f32::exp).collect::<Vec<f32>>();
+        assert!((magnitudes[0] - 1e-3).abs() <= 1e-6 && (magnitudes[1] - 1e3).abs() <= 1e-2);
+        // Middle coefficients too small to be vertices
+        let q = polynomial! { 4 => 1.0, 3 => 1e-2, 2 => 1e-3, 1 => 1e-2, 0 => 1e4 };
+        let vertices = q
+            .newton_polygon()
+            .iter()
+            .map(|v| v.0)
+            .collect::<Vec<usize>>();
+        assert_eq!(vertices, vec![0, 4]);
+        assert!(Polynomial::new().newton_polygon().is_empty());
+    }
+
+    #[test]
+    fn at_kahan() {
+        let p = polynomial! { 6 => 1e8, 5 => 1.0, 4 => 0.5, 3 => 0.25, 2 => -1e8 };
+        let mut terms = p.coeff_of_power.iter().collect::<Vec<(&usize, &f32)>>();
+        terms.sort_by(|a, b| b.0.cmp(a.0));
+        let naive = terms.iter().fold(0f32, |sum, (_, &coeff)| sum + coeff);
+        assert_eq!(naive, 0.0);
+        assert_eq!(p.at_kahan(1.0), 1.75);
+        let q = polynomial! { 3 => 1.0, 1 => -2.0, 0 => 0.5 };
+        assert_eq!(q.at_kahan(2.0), q.at(2.0));
+        assert_eq!(Polynomial::new().at_kahan(1.0), 0.0);
+    }
+
+    #[test]
+    fn plot_with_breaks() {
+        let p = polynomial! { 2 => 1.0, 0 => -1.0 };
+        let segments = p.clipped_segments(-3.0, 3.0, 61, 8.0);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].0.len(), 61);
+        let q = polynomial! { 3 => 1.0 };
+        let segments = q.clipped_segments(-3.0, 3.0, 61, 8.0);
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].0.first(), Some(&-2.0));
+        // Exceeds the clip in the middle, around 10 at 0
+        let r = polynomial! { 2 => -1.0, 0 => 10.0 };
+        let segments = r.clipped_segments(-3.0, 3.0, 61, 5.0);
+        assert_eq!(segments.len(), 2);
+        assert!(segments.iter().flat_map(|s| &s.1).all(|y| y.abs() <= 5.0));
+        let path = std::env::temp_dir().join("plot_with_breaks_test");
+        let path = path.to_str().unwrap();
+        assert_eq!(
+            Polynomial::plot_with_breaks(&[&p, &q], -3.0, 3.0, 61, 8.0, path),
+            Ok(())
+        );
+        std::fs::remove_file(format!("{}.gnuplot", path)).unwrap();
+        assert_eq!(
+            Polynomial::plot_with_breaks(&[&p], -3.0, 3.0, 1, 8.0, "should_not_exist"),
+            Err(PlotError::TooFewSamples)
+        );
+        assert_eq!(
+            Polynomial::plot_with_breaks(&[&p], 3.0, 3.0, 61, 8.0, "should_not_exist"),
+            Err(PlotError::EmptyRange)
+        );
+        assert_eq!(
+            Polynomial::plot_with_breaks(&[&p], -3.0, 3.0, 61, 0.0, "should_not_exist"),
+            Err(PlotError::NonPositiveClip)
+        );
+    }
+
+    #[test]
+    fn at_horner() {
+        let p = polynomial! { 3 => 1.0, 2 => -6.0, 1 => 12.0, 0 => -8.0 };
+        for &x in [-1.5, 0.0, 1.9, 2.0, 2.1, 3.25, 10.0].iter() {
+            let factored: f32 = (x - 2.0f32).powi(3);
+            assert!((p.at(x) - factored).abs() <= 1e-5 * factored.abs().max(1.0));
+        }
+        // Sparse terms with gaps
+        let q = polynomial! { 7 => 2.0, 3 => -1.0, 1 => 0.5 };
+        assert_eq!(q.at(2.0), 249.0);
+        assert_eq!(q.at(0.0), 0.0);
+        assert_eq!(polynomial! { 0 => 4.0 }.at(3.0), 4.0);
+        assert_eq!(Polynomial::new().at(3.0), 0.0);
+    }
+
+    #[test]
+    fn neg() {
+        let p = polynomial! { 2 => 1.0, 1 => -3.0, 0 => 5.0 };
+        assert_eq!(-&p, polynomial! { 2 => -1.0, 1 => 3.0, 0 => -5.0 });
+        assert_eq!(-p.clone(), polynomial! { 2 => -1.0, 1 => 3.0, 0 => -5.0 });
+        assert_eq!(-(-&p), p);
+        assert_eq!(-Polynomial::new(), Polynomial::new());
+        assert!((-Polynomial::new()).coeff_of_power.is_empty());
+    }
+
+    #[test]
+    fn roots_generalized() {
+        use crate::{dense, linalg};
+        // Roots near 1, 2 and -1e10
+        let p = polynomial! { 3 => 1e-10, 2 => 1.0, 1 => -3.0, 0 => 2.0 };
+        let a = p.to_dense_f64();
+        let newton = |mut x: f64| {
+            for _ in 0..50 {
+                let value = ((a[3] * x + a[2]) * x + a[1]) * x + a[0];
+                let slope = (3.0 * a[3] * x + 2.0 * a[2]) * x + a[1];
+                x -= value / slope;
+            }
+            x
+        };
+        let roots = p.roots_generalized(1e-14);
+        assert_eq!(roots.len(), 3);
+        assert!(roots[0].0 < -1e9);
+        let monic = linalg::hessenberg_eigenvalues(&dense::companion_matrix(&a)).unwrap();
+        for (root, guess) in roots[1..].iter().zip([1.0, 2.0].iter()) {
+            let exact = newton(*guess);
+            let generalized_error = (root.0 - exact).abs();
+            let monic_error = monic
+                .iter()
+                .map(|root| (root.re - exact).abs())
+                .fold(f64::INFINITY, f64::min);
+            assert!(generalized_error <= 1e-14 && root.1 == 0.0);
+            assert!(monic_error > 1e3 * generalized_error.max(f64::EPSILON));
+        }
+        // Complex roots of x^3 - 1
+        let roots = polynomial! { 3 => 1.0, 0 => -1.0 }.roots_generalized(1e-14);
+        let expected = [(-0.5, -0.75f64.sqrt()), (-0.5, 0.75f64.sqrt()), (1.0, 0.0)];
+        assert_eq!(roots.len(), 3);
+        for (root, expected) in roots.iter().zip(expected.iter()) {
+            assert!((root.0 - expected.0).abs() <= 1e-12 && (root.1 - expected.1).abs() <= 1e-12);
+        }
+        assert!(polynomial! { 0 => 2.0 }.roots_generalized(1e-14).is_empty());
+    }
+
+    #[test]
+    fn gauss_legendre_integrate() {
+        assert_eq!(Polynomial::legendre(0), polynomial! { 0 => 1.0 });
+        assert_eq!(Polynomial::legendre(2), polynomial! { 2 => 1.5, 0 => -0.5 });
+        assert_eq!(Polynomial::legendre(3), polynomial! { 3 => 2.5, 1 => -1.5 });
+        let p = polynomial! { 3 => 2.0, 2 => -3.0, 1 => 1.0, 0 => 4.0 };
+        let antiderivative = p.integral(0.0);
+        let exact = antiderivative.at(2.5) - antiderivative.at(-1.0);
+        assert!((p.gauss_legendre_integrate(-1.0, 2.5, 2) - exact).abs() <= 1e-5 * exact.abs());
+        // A 1-point rule is not exact for a cubic
+        assert!((p.gauss_legendre_integrate(-1.0, 2.5, 1) - exact).abs() > 1.0);
+        let q = Polynomial::legendre(9);
+        let antiderivative = q.integral(0.0);
+        let exact = antiderivative.at(1.0) - antiderivative.at(0.0);
+        assert!((q.gauss_legendre_integrate(0.0, 1.0, 5) - exact).abs() <= 1e-5);
+        for (x, _) in Polynomial::gauss_legendre_nodes(6) {
+            assert!(Polynomial::legendre(6).at(x as f32).abs() <= 1e-5);
+        }
+    }
+
+    #[test]
+    fn mul_scalar() {
+        let p = polynomial! { 2 => 1.0, 0 => 3.0 };
+        assert_eq!(p.clone() * 2.0, polynomial! { 2 => 2.0, 0 => 6.0 });
+        assert_eq!(&p * -0.5, polynomial! { 2 => -0.5, 0 => -1.5 });
+        assert_eq!(2.0 * &p, &p * 2.0);
+        assert_eq!(2.0 * p.clone(), &p * 2.0);
+        assert_eq!(p.clone() * 0.0, Polynomial::new());
+        assert!((&p * 0.0).coeff_of_power.is_empty());
+    }
+
+    #[test]
+    fn at_pair() {
+        let p = polynomial! { 5 => 0.5, 4 => -1.0, 3 => 2.0, 1 => -3.0, 0 => 1.5 };
+        // Gaps within each parity
+        let q = polynomial! { 11 => 0.25, 6 => -1.0, 3 => 2.0, 2 => 0.5 };
+        for &x in [-2.0, -0.3, 0.0, 0.7, 1.0, 3.0].iter() {
+            for p in [&p, &q].iter() {
+                let (at_x, at_minus_x) = p.at_pair(x);
+                assert!((at_x - p.at(x)).abs() <= 1e-5 * p.at(x).abs().max(1.0));
+                assert!((at_minus_x - p.at(-x)).abs() <= 1e-5 * p.at(-x).abs().max(1.0));
+            }
+        }
+        assert_eq!(polynomial! { 1 => 2.0 }.at_pair(3.0), (6.0, -6.0));
+        assert_eq!(polynomial! { 2 => 2.0 }.at_pair(3.0), (18.0, 18.0));
+        assert_eq!(Polynomial::new().at_pair(3.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn pow() {
+        let x_plus_1 = polynomial! { 1 => 1.0, 0 => 1.0 };
+        assert_eq!(
+            x_plus_1.pow(2),
+            polynomial! { 2 => 1.0, 1 => 2.0, 0 => 1.0 }
+        );
+        let x_minus_2 = polynomial! { 1 => 1.0, 0 => -2.0 };
+        assert_eq!(
+            x_minus_2.pow(3),
+            polynomial! { 3 => 1.0, 2 => -6.0, 1 => 12.0, 0 => -8.0 }
+        );
+        assert_eq!(x_minus_2.pow(1), x_minus_2);
+        assert_eq!(x_minus_2.pow(0), polynomial! { 0 => 1.0 });
+        assert_eq!(Polynomial::new().pow(0), polynomial! { 0 => 1.0 });
+        assert_eq!(Polynomial::new().pow(3), Polynomial::new());
+        assert_eq!(x_plus_1.pow(5).degree(), Some(5));
+    }
+
+    #[test]
+    fn reduce_degree() {
+        let close = |p: &Polynomial, q: &Polynomial| {
+            (0..=p.degree().max(q.degree()).unwrap_or(0)).all(|k| {
+                let pk = p.coeff_of_power.get(&k).cloned().unwrap_or(0.0);
+                let qk = q.coeff_of_power.get(&k).cloned().unwrap_or(0.0);
+                (pk - qk).abs() <= 1e-4
+            })
+        };
+        let p = polynomial! { 2 => 3.0, 1 => -1.0, 0 => 0.5 };
+        assert!(close(&p.reduce_degree(2, -1.0, 2.0, 101), &p));
+        // L2-best line to x^2 on [0, 1] is x - 1/6
+        let square = polynomial! { 2 => 1.0 };
+        assert!(close(
+            &square.reduce_degree(1, 0.0, 1.0, 101),
+            &polynomial! { 1 => 1.0, 0 => -1.0 / 6.0 }
+        ));
+        // Best constant is the mean value
+        assert!(close(
+            &square.reduce_degree(0, 0.0, 3.0, 101),
+            &polynomial! { 0 => 3.0 }
+        ));
+    }
+
+    #[test]
+    fn root_bounds_kojima() {
+        // (x - 1)(x - 2)(x + 4), Fujiwara's 2 sqrt(10) beats Kojima's 20
+        let p = polynomial! { 3 => 1.0, 2 => 1.0, 1 => -10.0, 0 => 8.0 };
+        let (lower, upper) = p.root_bounds_kojima().unwrap();
+        assert!(lower > 0.0 && lower <= 1.0);
+        assert!((upper - 2.0 * 10f32.sqrt()).abs() <= 1e-5);
+        // (x + 1)(x + 2)(x + 3)
+        let q = polynomial! { 3 => 1.0, 2 => 6.0, 1 => 11.0, 0 => 6.0 };
+        let (lower, upper) = q.root_bounds_kojima().unwrap();
+        assert_eq!(upper, 12.0);
+        assert!(lower > 0.25 && lower <= 1.0);
+        let with_zero_root = polynomial! { 2 => 1.0, 1 => -3.0 };
+        assert_eq!(with_zero_root.root_bounds_kojima().map(|b| b.0), Some(0.0));
+        assert_eq!(polynomial! { 0 => 2.0 }.root_bounds_kojima(), None);
+        assert_eq!(Polynomial::new().root_bounds_kojima(), None);
+    }
+
+    #[test]
+    fn terms() {
+        let p = polynomial! { 0 => 5.0, 2 => 1.0, 1 => -3.0 };
+        assert_eq!(
+            p.iter_sorted().collect::<Vec<(usize, f32)>>(),
+            vec![(2, 1.0), (1, -3.0), (0, 5.0)]
+        );
+        let mut terms = p.terms().collect::<Vec<(usize, f32)>>();
+        terms.sort_by_key(|&(power, _)| power);
+        assert_eq!(terms, vec![(0, 5.0), (1, -3.0), (2, 1.0)]);
+        assert_eq!(Polynomial::new().terms().count(), 0);
+        assert_eq!(Polynomial::new().iter_sorted().count(), 0);
+    }
+
+    #[test]
+    fn to_controllable_canonical() {
+        let num = polynomial! { 0 => 1.0 };
+        let den = polynomial! { 2 => 1.0, 1 => 3.0, 0 => 2.0 };
+        assert_eq!(
+            Polynomial::to_controllable_canonical(&num, &den),
+            Ok(StateSpace {
+                a: vec![vec![0.0, 1.0], vec![-2.0, -3.0]],
+                b: vec![0.0, 1.0],
+                c: vec![1.0, 0.0],
+                d: 0.0,
+            })
+        );
+        // (2s^2 + 1) / (2s^2 + 6s + 4) = 1 + (-3s - 1.5) / (s^2 + 3s + 2)
+        let num = polynomial! { 2 => 2.0, 0 => 1.0 };
+        let den = polynomial! { 2 => 2.0, 1 => 6.0, 0 => 4.0 };
+        let ss = Polynomial::to_controllable_canonical(&num, &den).unwrap();
+        assert_eq!(ss.a, vec![vec![0.0, 1.0], vec![-2.0, -3.0]]);
+        assert_eq!((ss.c, ss.d), (vec![-1.5, -3.0], 1.0));
+        assert_eq!(
+            Polynomial::to_controllable_canonical(&den, &num.derivative()),
+            Err(SsError::Improper)
+        );
+        assert_eq!(
+            Polynomial::to_controllable_canonical(&num, &Polynomial::new()),
+            Err(SsError::ZeroDenominator)
+        );
+    }
+
+    #[test]
+    fn leading_coefficient() {
+        assert_eq!(
+            polynomial! { 3 => 2.0, 1 => 5.0 }.leading_coefficient(),
+            Some(2.0)
+        );
+        assert_eq!(Polynomial::new().leading_coefficient(), None);
+    }
+
+    #[test]
+    fn root_near() {
+        let p = polynomial! { 2 => 1.0, 0 => -2.0 };
+        let root = p.root_near(1.4, 1e-6, 50).unwrap();
+        assert!((root - 2f32.sqrt()).abs() < 1e-6);
+        let root = p.root_near(-1.0, 1e-6, 50).unwrap();
+        assert!((root + 2f32.sqrt()).abs() < 1e-6);
+        // First Newton step from 0.5 overshoots the root at 1 to 1.67, bracketing it
+        let p = polynomial! { 3 => 1.0, 0 => -1.0 };
+        let root = p.root_near(0.5, 1e-6, 50).unwrap();
+        assert!((root - 1.0).abs() < 1e-6);
+        assert_eq!(
+            polynomial! { 2 => 1.0, 0 => 1.0 }.root_near(0.0, 1e-6, 50),
+            None
+        );
+    }
+
+    #[test]
+    fn isolate_positive_roots() {
+        // (x - 1)(x - 2)(x - 3)
+        let p = polynomial! { 3 => 1.0, 2 => -6.0, 1 => 11.0, 0 => -6.0 };
+        let intervals = p.isolate_positive_roots().unwrap();
+        assert_eq!(intervals.len(), 3);
+        for (&(lo, hi), root) in intervals.iter().zip([1.0, 2.0, 3.0]) {
+            assert!(0.0 < lo && lo <= root && root <= hi);
+        }
+        for pair in intervals.windows(2) {
+            assert!(pair[0].1 <= pair[1].0);
+        }
+        // x^2 (x + 1)^2 (x - 5)
+        let p = &(&polynomial! { 2 => 1.0 } * &polynomial! { 2 => 1.0, 1 => 2.0, 0 => 1.0 })
+            * &polynomial! { 1 => 1.0, 0 => -5.0 };
+        let intervals = p.isolate_positive_roots().unwrap();
+        assert_eq!(intervals.len(), 1);
+        assert!(intervals[0].0 <= 5.0 && 5.0 <= intervals[0].1);
+        assert_eq!(Polynomial::new().isolate_positive_roots(), Some(vec![]));
+    }
+
+    #[test]
+    fn refine_root() {
+        // (x - 2)(x - 3)
+        let p = polynomial! { 2 => 1.0, 1 => -5.0, 0 => 6.0 };
+        let root = p.refine_root(2.1, 50, 1e-7).unwrap();
+        assert!((root - 2.0).abs() < 1e-6);
+        // Derivative vanishes at the seed
+        assert_eq!(p.refine_root(2.5, 50, 1e-7), None);
+        assert_eq!(
+            polynomial! { 2 => 1.0, 0 => 1.0 }.refine_root(0.5, 50, 1e-7),
+            None
+        );
+    }
+
+    #[test]
+    fn tolerant_polynomial() {
+        let p = polynomial! { 2 => 1.0, 1 => -3.0, 0 => 0.5 };
+        let mut q = p.clone();
+        q.add_term(1, 1e-7);
+        q.add_term(0, -1e-7);
+        assert_ne!(p, q);
+        let mut polys = vec![p.clone().with_tolerance(1e-5), q.with_tolerance(1e-5)];
+        polys.dedup();
+        assert_eq!(polys.len(), 1);
+        let set = vec![p.clone(), &p * 1.0000001, &p + &polynomial! { 3 => 1e-7 }]
+            .into_iter()
+            .map(|poly| poly.with_tolerance(1e-5))
+            .collect::<HashSet<_>>();
+        assert_eq!(set.len(), 1);
+        assert_ne!(
+            p.clone().with_tolerance(1e-5),
+            (&p * 1.01).with_tolerance(1e-5)
+        );
+    }
+
+    #[test]
+    fn root_in_bracket() {
+        let p = polynomial! { 2 => 1.0, 0 => -2.0 };
+        let root = p.root_in_bracket(1.0, 2.0, 1e-6).unwrap();
+        assert!((root - 2f32.sqrt()).abs() < 1e-5);
+        assert_eq!(p.root_in_bracket(2.0, 3.0, 1e-6), None);
+        // Zero tolerance stops once the bracket is as narrow as f32 allows
+        let root = p.root_in_bracket(-2.0, 0.0, 0.0).unwrap();
+        assert!((root + 2f32.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sample_derivative_grid() {
+        let p = polynomial! { 3 => 1.0, 2 => -2.0, 0 => 4.0 };
+        let derivative = p.derivative();
+        let (xs, slopes) = p.sample_derivative_grid(-2.0, 3.0, 11).unwrap();
+        assert_eq!(xs.len(), 11);
+        assert_eq!((xs[0], xs[10]), (-2.0, 3.0));
+        for (&x, &slope) in xs.iter().zip(slopes.iter()) {
+            assert!((slope - derivative.at(x)).abs() < 1e-5);
+        }
+        assert_eq!(
+            p.sample_derivative_grid(1.0, 1.0, 11),
+            Err(SampleError::EmptyRange)
+        );
+        assert_eq!(
+            p.sample_derivative_grid(0.0, 1.0, 1),
+            Err(SampleError::TooFewSamples)
+        );
+    }
+
+    #[test]
+    fn complex_roots() {
+        let close = |roots: Vec<(f32, f32)>, expected: &[(f32, f32)]| {
+            assert_eq!(roots.len(), expected.len());
+            for (&(re, im), &(expected_re, expected_im)) in roots.iter().zip(expected) {
+                assert!(
+                    (re - expected_re).abs() < 1e-4 && (im - expected_im).abs() < 1e-4,
+                    "{:?}",
+                    roots
+                );
+            }
+        };
+        close(
+            polynomial! { 2 => 1.0, 0 => 1.0 }.complex_roots(),
+            &[(0.0, -1.0), (0.0, 1.0)],
+        );
+        // (x - 1 - 2i)(x - 1 + 2i)
+        close(
+            polynomial! { 2 => 2.0, 1 => -4.0, 0 => 10.0 }.complex_roots(),
+            &[(1.0, -2.0), (1.0, 2.0)],
+        );
+        // (x - 3)(x + 0.5)
+        close(
+            polynomial! { 2 => 1.0, 1 => -2.5, 0 => -1.5 }.complex_roots(),
+            &[(-0.5, 0.0), (3.0, 0.0)],
+        );
+        close(
+            polynomial! { 1 => 4.0, 0 => -2.0 }.complex_roots(),
+            &[(0.5, 0.0)],
+        );
+        assert!(polynomial! { 0 => 3.0 }.complex_roots().is_empty());
+        assert!(Polynomial::new().complex_roots().is_empty());
+    }
+
+    #[test]
+    fn lerp() {
+        let p = polynomial! { 2 => 1.0, 1 => -2.0, 0 => 3.0 };
+        let q = polynomial! { 3 => 4.0, 1 => 2.0, 0 => 1.0 };
+        assert_eq!(p.lerp(&q, 0.0), p);
+        assert_eq!(p.lerp(&q, 1.0), q);
+        assert_eq!(
+            p.lerp(&q, 0.5),
+            polynomial! { 3 => 2.0, 2 => 0.5, 0 => 2.0 }
+        );
+        let mut r = p.clone();
+        r.add_scaled(&q, -2.0);
+        assert_eq!(r, polynomial! { 3 => -8.0, 2 => 1.0, 1 => -6.0, 0 => 1.0 });
+    }
+
+    #[test]
+    fn compose() {
+        let square = polynomial! { 2 => 1.0, 0 => 0.0 };
+        assert_eq!(
+            square.compose(&polynomial! { 1 => 1.0, 0 => 1.0 }),
+            polynomial! { 2 => 1.0, 1 => 2.0, 0 => 1.0 }
+        );
+        let p = polynomial! { 3 => 1.0, 1 => -2.0, 0 => 5.0 };
+        assert_eq!(
+            p.compose(&polynomial! { 0 => 2.0 }),
+            polynomial! { 0 => 9.0 }
+        );
+        assert_eq!(p.compose(&Polynomial::new()), polynomial! { 0 => 5.0 });
+        assert_eq!(Polynomial::new().compose(&p), Polynomial::new());
+    }
+
+    #[test]
+    fn shift() {
+        assert_eq!(
+            polynomial! { 2 => 1.0 }.shift(1.0),
+            polynomial! { 2 => 1.0, 1 => 2.0, 0 => 1.0 }
+        );
+        let p = polynomial! { 4 => 0.5, 3 => -1.0, 1 => 2.0, 0 => -3.0 };
+        for &a in [-1.3, 0.0, 0.7, 2.1].iter() {
+            let shifted = p.shift(a);
+            for &x in [-1.9, -0.4, 0.3, 1.1, 1.6].iter() {
+                assert!((shifted.at(x) - p.at(x + a)).abs() < 1e-4);
+            }
+        }
+        assert_eq!(Polynomial::new().shift(2.0), Polynomial::new());
+    }
+
+    #[test]
+    fn scale_x() {
+        let p = polynomial! { 2 => 1.0, 1 => 1.0, 0 => 1.0 };
+        assert_eq!(p.scale_x(2.0), polynomial! { 2 => 4.0, 1 => 2.0, 0 => 1.0 });
+        assert_eq!(p.scale_x(0.0), polynomial! { 0 => 1.0 });
+        assert_eq!(p.scale_x(1.0), p);
+        let p = polynomial! { 5 => -0.25, 3 => 1.5, 0 => 2.0 };
+        for &c in [-1.5, 0.5, 3.0].iter() {
+            let scaled = p.scale_x(c);
+            for &x in [-1.2, 0.4, 0.9].iter() {
+                assert!((scaled.at(x) - p.at(c * x)).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn gcd() {
+        // (x - 1)(x - 2) and (x - 1)(x - 3)
+        let p = polynomial! { 2 => 1.0, 1 => -3.0, 0 => 2.0 };
+        let q = polynomial! { 2 => 1.0, 1 => -4.0, 0 => 3.0 };
+        assert_eq!(p.gcd(&q), polynomial! { 1 => 1.0, 0 => -1.0 });
+        assert_eq!((&p * 2.0).gcd(&q), polynomial! { 1 => 1.0, 0 => -1.0 });
+        // Coprime
+        let r = polynomial! { 2 => 1.0, 0 => 1.0 };
+        assert_eq!(p.gcd(&r), polynomial! { 0 => 1.0 });
+        assert_eq!(Polynomial::new().gcd(&(&p * 3.0)), p);
+        assert_eq!(p.gcd(&Polynomial::new()), p);
+        assert_eq!(Polynomial::new().gcd(&Polynomial::new()), Polynomial::new());
+        // (x - 0.1)(x + 0.7) and (x - 0.1)(x - 2.3), remainders do not cancel exactly in f32
+        let p = &polynomial! { 1 => 1.0, 0 => -0.1 } * &polynomial! { 1 => 1.0, 0 => 0.7 };
+        let q = &polynomial! { 1 => 1.0, 0 => -0.1 } * &polynomial! { 1 => 1.0, 0 => -2.3 };
+        let g = p.gcd(&q);
+        assert_eq!(g.degree(), Some(1));
+        assert!((g.at(0.0) + 0.1).abs() < 1e-5);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            "3x^2 - 5x + 6".parse::<Polynomial>(),
+            Ok(polynomial! { 2 => 3.0, 1 => -5.0, 0 => 6.0 })
+        );
+        assert_eq!(
+            "x^2 + 1".parse::<Polynomial>(),
+            Ok(polynomial! { 2 => 1.0, 0 => 1.0 })
+        );
+        assert_eq!(
+            "-2x^{3} + x".parse::<Polynomial>(),
+            Ok(polynomial! { 3 => -2.0, 1 => 1.0 })
+        );
+        assert_eq!(
+            "0.5*x - x + 1.5e-1".parse::<Polynomial>(),
+            Ok(polynomial! { 1 => -0.5, 0 => 0.15 })
+        );
+        assert_eq!("0".parse::<Polynomial>(), Ok(Polynomial::new()));
+        let p = polynomial! { 4 => -0.25, 2 => 1.0, 1 => -3.5, 0 => 7.0 };
+        assert_eq!(format!("{}", p).parse::<Polynomial>(), Ok(p));
+        for malformed in ["", "3y^2", "x^", "x^{2", "2x3", "x^-1", "+", "1.2.3"].iter() {
+            assert!(malformed.parse::<Polynomial>().is_err(), "{}", malformed);
+        }
+    }
+
+    #[test]
+    fn display() {
+        let p = polynomial! { 2 => 1.0, 1 => -1.0, 0 => 6.0 };
+        assert_eq!(format!("{}", p), "x^2 - x + 6");
+        assert_eq!(p.to_latex(), "x^{2} - x + 6");
+        let p = polynomial! { 12 => -1.0, 3 => 2.5, 0 => -1.0 };
+        assert_eq!(format!("{}", p), "-x^12 + 2.5x^3 - 1");
+        assert_eq!(p.to_latex(), "-x^{12} + 2.5x^{3} - 1");
+        assert_eq!(format!("{}", Polynomial::new()), "0");
+        assert_eq!(Polynomial::new().to_latex(), "0");
+    }
+
+    #[test]
+    fn from_dense() {
+        let p = Polynomial::from(vec![6.0, -5.0, 1.0]);
+        assert_eq!(p, polynomial! { 2 => 1.0, 1 => -5.0, 0 => 6.0 });
+        assert_eq!(p.to_dense(), vec![6.0, -5.0, 1.0]);
+        let p = polynomial! { 4 => 2.0, 1 => -1.0 };
+        assert_eq!(p.to_dense(), vec![0.0, -1.0, 0.0, 0.0, 2.0]);
+        assert_eq!(Polynomial::from(p.to_dense()), p);
+        assert_eq!(
+            Polynomial::from(vec![0.0, 3.0, 0.0, 0.0]),
+            polynomial! { 1 => 3.0 }
+        );
+        assert_eq!(Polynomial::from(vec![]), Polynomial::new());
+        assert!(Polynomial::new().to_dense().is_empty());
+    }
+
+    #[test]
+    fn index() {
+        let p = polynomial! { 2 => 3.0, 0 => -1.0 };
+        assert_eq!(p[2], 3.0);
+        assert_eq!(p[0], -1.0);
+        assert_eq!(p[1], 0.0);
+        assert_eq!(p[5], 0.0);
+        assert_eq!(p.coeff(2), 3.0);
+        assert_eq!(p.coeff(5), 0.0);
+        assert_eq!(Polynomial::new()[0], 0.0);
+    }
+
+    #[test]
+    fn derivative_n() {
+        let p = polynomial! { 3 => 1.0, 2 => -6.0, 1 => 12.0, 0 => -8.0 };
+        assert_eq!(p.derivative_n(0), p);
+        assert_eq!(p.derivative_n(1), p.derivative());
+        assert_eq!(p.derivative_n(2), p.derivative().derivative());
+        assert_eq!(p.derivative_n(3), polynomial! { 0 => 6.0 });
+        assert_eq!(p.derivative_n(4), Polynomial::new());
+        assert_eq!(Polynomial::new().derivative_n(2), Polynomial::new());
+    }
+
+    #[test]
+    fn evaluate_all() {
+        let p = polynomial! { 1 => 2.0, 0 => 1.0 };
+        assert_eq!(p.evaluate_all(&[0.0, 1.0, 2.0]), vec![1.0, 3.0, 5.0]);
+        let p = polynomial! { 7 => 0.5, 2 => -3.0, 0 => 1.0 };
+        let xs = [1.5, -0.5, 0.0, 2.0, -1.25];
+        let ys = p.evaluate_all(&xs);
+        assert_eq!(ys.len(), xs.len());
+        for (&x, &y) in xs.iter().zip(ys.iter()) {
+            assert_eq!(y, p.at(x));
+        }
+        assert!(p.evaluate_all(&[]).is_empty());
+    }
+
+    #[test]
+    fn interpolate() {
+        let points = [(0.0, 1.0), (1.0, 3.0), (2.0, 7.0)];
+        let p = Polynomial::interpolate(&points).unwrap();
+        for &(x, y) in points.iter() {
+            assert!((p.at(x) - y).abs() < 1e-4);
+        }
+        assert_eq!(p, polynomial! { 2 => 1.0, 1 => 1.0, 0 => 1.0 });
+        let points = [(-1.5, 2.0), (0.5, -1.0), (3.0, 0.25), (1.25, 4.0)];
+        let p = Polynomial::interpolate(&points).unwrap();
+        assert!(p.degree().unwrap() <= 3);
+        for &(x, y) in points.iter() {
+            assert!((p.at(x) - y).abs() < 1e-4);
+        }
+        assert!(Polynomial::interpolate(&[(1.0, 2.0), (1.0, 3.0)]).is_err());
+        assert!(Polynomial::interpolate(&[]).is_err());
+    }
+
+    #[test]
+    fn root_bound() {
+        let polys = [
+            polynomial! { 3 => 1.0, 2 => -6.0, 1 => 11.0, 0 => -6.0 },
+            polynomial! { 2 => 1.0, 1 => -1100.0, 0 => 100000.0 },
+            polynomial! { 4 => -2.0, 2 => 3.0, 0 => 1.0 },
+            polynomial! { 5 => 0.5, 1 => -4.0 },
+            polynomial! { 1 => 3.0, 0 => 7.0 },
+        ];
+        for p in polys.iter() {
+            let bound = p.root_bound().unwrap();
+            let roots = p.real_roots(0.01);
+            assert!(!roots.is_empty());
+            for root in roots {
+                assert!(root.abs() < bound);
+            }
+        }
+        assert_eq!(
+            polynomial! { 2 => 2.0, 1 => -3.0, 0 => 1.0 }.root_bound(),
+            Some(2.5)
+        );
+        assert_eq!(polynomial! { 0 => 2.0 }.root_bound(), None);
+        assert_eq!(Polynomial::new().root_bound(), None);
+    }
+
+    #[test]
+    fn real_roots_bounded() {
+        let p = polynomial! { 2 => 1.0, 1 => -1100.0, 0 => 100000.0 };
+        let roots = p.real_roots_bounded(0.1);
+        assert_eq!(roots.len(), 2);
+        assert!((roots[0] - 100.0).abs() < 1e-2);
+        assert!((roots[1] - 1000.0).abs() < 1e-1);
+        // (x - 1)(x - 2)(x - 3)
+        let p = polynomial! { 3 => 1.0, 2 => -6.0, 1 => 11.0, 0 => -6.0 };
+        assert_eq!(p.real_roots_bounded(0.01).len(), 3);
+        // Roots -1, 0 and 1 fall exactly on the grid
+        let p = polynomial! { 3 => 1.0, 1 => -1.0 };
+        assert_eq!(p.real_roots_bounded(0.5), vec![-1.0, 0.0, 1.0]);
+        assert!(polynomial! { 2 => 1.0, 0 => 1.0 }
+            .real_roots_bounded(0.01)
+            .is_empty());
+        assert!(polynomial! { 0 => 1.0 }.real_roots_bounded(0.01).is_empty());
+    }
+
+    #[test]
+    fn sturm_root_count() {
+        let p = polynomial! { 3 => 1.0, 1 => -1.0 };
+        assert_eq!(p.sturm_root_count(-2.0, 2.0), 3);
+        assert_eq!(p.sturm_root_count(0.5, 2.0), 1);
+        assert_eq!(p.sturm_root_count(-0.5, 0.5), 1);
+        assert_eq!(p.sturm_root_count(2.0, -2.0), 0);
+        // (x - 1)^2 (x + 2), the double root counts once
+        let p = polynomial! { 3 => 1.0, 1 => -3.0, 0 => 2.0 };
+        assert_eq!(p.sturm_root_count(-3.0, 3.0), 2);
+        assert_eq!(p.sturm_root_count(0.0, 3.0), 1);
+        // (x - 1)(x - 2)(x - 3)(x^2 + 1)
+        let p = &polynomial! { 3 => 1.0, 2 => -6.0, 1 => 11.0, 0 => -6.0 }
+            * &polynomial! { 2 => 1.0, 0 => 1.0 };
+        assert_eq!(p.sturm_root_count(-10.0, 10.0), 3);
+        assert_eq!(p.sturm_root_count(1.5, 2.5), 1);
+        assert_eq!(
+            polynomial! { 2 => 1.0, 0 => 1.0 }.sturm_root_count(-5.0, 5.0),
+            0
+        );
+        assert_eq!(Polynomial::new().sturm_root_count(-1.0, 1.0), 0);
+    }
+
+    #[test]
+    fn square_free() {
+        // (x - 1)^2 (x - 2)
+        let p = polynomial! { 3 => 1.0, 2 => -4.0, 1 => 5.0, 0 => -2.0 };
+        let q = p.square_free();
+        assert_eq!(q.degree(), Some(2));
+        for (&coeff, expected) in q.to_dense().iter().zip([2.0, -3.0, 1.0].iter()) {
+            assert!((coeff - expected).abs() < 1e-4);
+        }
+        // The double root at 1 is a sign change of q
+        let roots = q.real_roots_bounded(0.01);
+        assert_eq!(roots.len(), 2);
+        for (root, expected) in roots.iter().zip([1.0, 2.0].iter()) {
+            assert!((root - expected).abs() < 1e-3);
+            assert!(p.at(*root).abs() < 1e-4);
+        }
+        // Already square-free
+        let p = polynomial! { 2 => 2.0, 0 => -8.0 };
+        assert_eq!(p.square_free(), p);
+        assert_eq!(
+            polynomial! { 0 => 3.0 }.square_free(),
+            polynomial! { 0 => 3.0 }
+        );
+        assert_eq!(Polynomial::new().square_free(), Polynomial::new());
+    }
+
+    #[test]
+    fn divide_linear() {
+        let p = polynomial! { 2 => 1.0, 1 => -5.0, 0 => 6.0 };
+        let (quotient, remainder) = p.divide_linear(2.0);
+        assert_eq!(quotient, polynomial! { 1 => 1.0, 0 => -3.0 });
+        assert_eq!(remainder, 0.0);
+        let p = polynomial! { 4 => 2.0, 2 => -1.0, 1 => 3.0, 0 => 5.0 };
+        for &r in [-1.5, 0.0, 2.0, 3.0].iter() {
+            let (quotient, remainder) = p.divide_linear(r);
+            assert_eq!(remainder, p.at(r));
+            let product = &quotient * &polynomial! { 1 => 1.0, 0 => -r };
+            assert_eq!(&product + &polynomial! { 0 => remainder }, p);
+        }
+        assert_eq!(
+            polynomial! { 0 => 4.0 }.divide_linear(1.0),
+            (Polynomial::new(), 4.0)
+        );
+        assert_eq!(
+            Polynomial::new().divide_linear(1.0),
+            (Polynomial::new(), 0.0)
+        );
+    }
+
+    #[test]
+    fn div_rem() {
+        let cases = [
+            (Polynomial::new(), polynomial! { 1 => 1.0, 0 => -2.0 }),
+            (
+                polynomial! { 2 => 1.0, 1 => -5.0, 0 => 6.0 },
+                polynomial! { 1 => 1.0, 0 => -2.0 },
+            ),
+            (
+                polynomial! { 3 => 2.0, 2 => -5.0, 1 => -1.0, 0 => 3.0 },
+                polynomial! { 1 => 1.0, 0 => 3.0 },
+            ),
+            (
+                polynomial! { 4 => 6.0, 3 => 5.0, 1 => 4.0, 0 => -4.0 },
+                polynomial! { 2 => 2.0, 1 => 1.0, 0 => -1.0 },
+            ),
+            (polynomial! { 1 => 1.0 }, polynomial! { 3 => 1.0, 0 => 1.0 }),
+        ];
+        for (p, q) in cases.iter() {
+            assert_eq!(p.div_rem(q), (p / q, p % q));
+        }
+        assert_eq!(
+            cases[3].0.div_rem(&cases[3].1),
+            (
+                polynomial! { 2 => 3.0, 1 => 1.0, 0 => 1.0 },
+                polynomial! { 1 => 4.0, 0 => -3.0 }
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_rem_with_zero_polynomial() {
+        let p = polynomial! { 2 => 1.0 };
+        let _ = p.div_rem(&Polynomial::new());
+    }
+
+    #[test]
+    fn div_rem_non_integer_coefficients() {
+        let p = polynomial! { 2 => 0.3, 1 => 0.6, 0 => 0.3 };
+        let q = polynomial! { 1 => 0.3, 0 => 0.3 };
+        let (quotient, remainder) = p.div_rem(&q);
+        assert_eq!(quotient.degree(), Some(1));
+        assert!((quotient[1] - 1.0).abs() < 1e-5 && (quotient[0] - 1.0).abs() < 1e-5);
+        assert!(remainder.degree() < q.degree());
+        assert!(remainder[0].abs() < 1e-5);
+        let p = polynomial! { 3 => 0.1, 2 => 0.7, 1 => 0.3, 0 => 0.2 };
+        let q = polynomial! { 2 => 1.5, 0 => 0.9 };
+        let (quotient, remainder) = p.div_rem(&q);
+        assert!(remainder.degree() < q.degree());
+        let reconstructed = &(&quotient * &q) + &remainder;
+        for power in 0..=3 {
+            assert!((reconstructed[power] - p[power]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn approx_eq() {
+        let p = polynomial! { 2 => 1.0, 1 => -0.5, 0 => 3.0 };
+        let q = polynomial! { 2 => 1.0 + 1e-7, 1 => -0.5, 0 => 3.0 - 1e-7, 3 => 1e-7 };
+        assert!(p.approx_eq(&q, 1e-6));
+        assert!(q.approx_eq(&p, 1e-6));
+        let r = polynomial! { 2 => 1.1, 1 => -0.5, 0 => 3.0 };
+        assert!(!p.approx_eq(&r, 1e-6));
+        assert!(!p.approx_eq(&polynomial! { 2 => 1.0, 1 => -0.5 }, 1e-6));
+        assert!(!p.approx_eq(&(&p + &polynomial! { 5 => 0.1 }), 1e-6));
+        assert!(Polynomial::new().approx_eq(&Polynomial::new(), 0.0));
+    }
+
+    #[test]
+    fn cmp_by_degree() {
+        let mut polys = vec![
+            polynomial! { 2 => 1.0 },
+            polynomial! { 3 => 1.0 },
+            polynomial! { 0 => 5.0 },
+            polynomial! { 1 => 1.0 },
+            Polynomial::new(),
+        ];
+        polys.sort_by(|p, q| p.cmp_by_degree(q));
+        assert_eq!(
+            polys,
+            vec![
+                Polynomial::new(),
+                polynomial! { 0 => 5.0 },
+                polynomial! { 1 => 1.0 },
+                polynomial! { 2 => 1.0 },
+                polynomial! { 3 => 1.0 },
+            ]
+        );
+        let p = polynomial! { 2 => 1.0, 1 => 2.0 };
+        let q = polynomial! { 2 => 1.0, 0 => 3.0 };
+        assert_eq!(p.cmp_by_degree(&q), Ordering::Greater);
+        assert_eq!(q.cmp_by_degree(&p), Ordering::Less);
+        assert_eq!(p.cmp_by_degree(&p.clone()), Ordering::Equal);
+        assert_eq!(
+            polynomial! { 2 => -1.0 }.cmp_by_degree(&polynomial! { 2 => 1.0 }),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn predicates() {
+        let zero = Polynomial::new();
+        let constant = polynomial! { 0 => 3.0 };
+        let one = polynomial! { 0 => 1.0 };
+        let monic = polynomial! { 2 => 1.0, 0 => -4.0 };
+        let other = polynomial! { 3 => 2.0, 1 => 1.0 };
+        assert!(zero.is_zero());
+        assert!(!constant.is_zero() && !monic.is_zero());
+        assert!(polynomial! { 2 => 0.0 }.is_zero());
+        assert!(zero.is_constant() && constant.is_constant());
+        assert!(!monic.is_constant() && !other.is_constant());
+        assert!(monic.is_monic() && one.is_monic());
+        assert!(!zero.is_monic() && !constant.is_monic() && !other.is_monic());
+    }
+
+    #[test]
+    fn default_and_sum() {
+        assert_eq!(Polynomial::default(), Polynomial::new());
+        let polys = vec![
+            polynomial! { 2 => 1.0, 0 => 1.0 },
+            polynomial! { 1 => -3.0, 0 => 2.0 },
+            polynomial! { 2 => -1.0, 3 => 0.5 },
+        ];
+        let expected = polynomial! { 3 => 0.5, 1 => -3.0, 0 => 3.0 };
+        assert_eq!(polys.iter().sum::<Polynomial>(), expected);
+        assert_eq!(polys.into_iter().sum::<Polynomial>(), expected);
+        assert_eq!(
+            Vec::<Polynomial>::new().into_iter().sum::<Polynomial>(),
+            Polynomial::new()
+        );
+    }
+
+    #[test]
+    fn from_iter_and_extend() {
+        let p = vec![(1, 1.0), (1, 2.0), (0, 5.0)]
+            .into_iter()
+            .collect::<Polynomial>();
+        assert_eq!(p, polynomial! { 1 => 3.0, 0 => 5.0 });
+        let p = vec![(2, 1.5), (0, 1.0), (2, -1.5)]
+            .into_iter()
+            .collect::<Polynomial>();
+        assert_eq!(p, polynomial! { 0 => 1.0 });
+        let mut q = polynomial! { 3 => 1.0, 1 => -2.0 };
+        q.extend(vec![(1, 2.0), (3, 1.0), (4, 0.0), (0, -7.0)]);
+        assert_eq!(q, polynomial! { 3 => 2.0, 0 => -7.0 });
+        assert_eq!(q.terms().collect::<Polynomial>(), q);
+    }
 }